@@ -0,0 +1,51 @@
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crate::{embedded_input, solve};
+
+/// Times every implemented part of `days` and prints a summary table.
+///
+/// This is a quick overview, use `cargo bench` for statistically sound
+/// numbers. Either way, build with `--release`.
+pub fn run(days: RangeInclusive<u8>, iterations: u32) -> color_eyre::Result<()> {
+    println!(
+        "{:>3} {:>4} {:>12} {:>12} {:>12}",
+        "day", "part", "min", "median", "mean"
+    );
+
+    let mut total = Duration::ZERO;
+    for day in days {
+        let input = embedded_input(day);
+        for part in 1..=2 {
+            // also serves as a warm-up run
+            if solve(day, part, input)?.is_none() {
+                continue;
+            }
+
+            let mut timings = (0..iterations.max(1))
+                .map(|_| {
+                    let start = Instant::now();
+                    solve(day, part, input).map(|_| start.elapsed())
+                })
+                .collect::<color_eyre::Result<Vec<_>>>()?;
+            timings.sort();
+
+            let mean = timings.iter().sum::<Duration>() / timings.len() as u32;
+            let median = timings[timings.len() / 2];
+            total += mean;
+
+            println!(
+                "{day:>3} {part:>4} {:>12} {:>12} {:>12}",
+                format!("{:.1?}", timings[0]),
+                format!("{median:.1?}"),
+                format!("{mean:.1?}"),
+            );
+        }
+    }
+
+    println!("{:>47}", format!("total {total:.1?}"));
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::ops::RangeInclusive;
+
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::bail;
+use common::InputArgs;
+
+mod bench;
+
+const DAYS: RangeInclusive<u8> = 1..=10;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day when no day is given
+    Run {
+        day: Option<u8>,
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Time every part of one day, or of every day, on the embedded inputs
+    Bench {
+        day: Option<u8>,
+        /// How many times each part is run
+        #[arg(long, short = 'n', default_value_t = 100)]
+        iterations: u32,
+    },
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    match Cli::parse().command {
+        Command::Run { day, part, input } => {
+            if day.is_none() && input.input.is_some() {
+                bail!("--input needs a day");
+            }
+
+            for day in select_days(day)? {
+                let input = input.load(embedded_input(day), Some(example_input(day)))?;
+                let input = input.as_ref();
+                for p in part.map_or(1..=2, |part| part..=part) {
+                    match solve(day, p, input)? {
+                        Some(answer) if answer.contains('\n') => {
+                            print!("Day {day} part {p}:\n{answer}")
+                        }
+                        Some(answer) => println!("Day {day} part {p}: {answer}"),
+                        // only complain about missing parts that were asked for explicitly
+                        None if part.is_some() => bail!("day {day} part {p} is not implemented"),
+                        None => {}
+                    }
+                }
+            }
+        }
+        Command::Bench { day, iterations } => bench::run(select_days(day)?, iterations)?,
+    }
+
+    Ok(())
+}
+
+fn select_days(day: Option<u8>) -> color_eyre::Result<RangeInclusive<u8>> {
+    match day {
+        Some(day) if DAYS.contains(&day) => Ok(day..=day),
+        Some(day) => bail!("there is no solution for day {day}"),
+        None => Ok(DAYS),
+    }
+}
+
+fn embedded_input(day: u8) -> &'static str {
+    match day {
+        1 => day1::INPUT,
+        2 => day2::INPUT,
+        3 => day3::INPUT,
+        4 => day4::INPUT,
+        5 => day5::INPUT,
+        6 => day6::INPUT,
+        7 => day7::INPUT,
+        8 => day8::INPUT,
+        9 => day9::INPUT,
+        10 => day10::INPUT,
+        _ => unreachable!("day {day} is out of range"),
+    }
+}
+
+fn example_input(day: u8) -> &'static str {
+    match day {
+        1 => day1::EXAMPLE_INPUT,
+        2 => day2::EXAMPLE_INPUT,
+        3 => day3::EXAMPLE_INPUT,
+        4 => day4::EXAMPLE_INPUT,
+        5 => day5::EXAMPLE_INPUT,
+        6 => day6::EXAMPLE_INPUT,
+        7 => day7::EXAMPLE_INPUT,
+        8 => day8::EXAMPLE_INPUT,
+        9 => day9::EXAMPLE_INPUT,
+        10 => day10::EXAMPLE_INPUT,
+        _ => unreachable!("day {day} is out of range"),
+    }
+}
+
+fn solve(day: u8, part: u8, input: &str) -> color_eyre::Result<Option<String>> {
+    let answer = match (day, part) {
+        (1, 1) => day1::part1(input)?.to_string(),
+        (1, 2) => day1::part2(input)?.to_string(),
+        (2, 1) => day2::part1(input)?.to_string(),
+        (2, 2) => day2::part2(input)?.to_string(),
+        (3, 1) => day3::part1(input)?.to_string(),
+        (3, 2) => day3::part2(input)?.to_string(),
+        (4, 1) => day4::part1(input)?.to_string(),
+        (4, 2) => day4::part2(input)?.to_string(),
+        (5, 1) => day5::part1(input)?,
+        (5, 2) => day5::part2(input)?,
+        (6, 1) => day6::part1(input)?.to_string(),
+        (6, 2) => day6::part2(input)?.to_string(),
+        (7, 1) => day7::part1(input)?.to_string(),
+        (7, 2) => day7::part2(input)?.to_string(),
+        (8, 1) => day8::part1(input)?.to_string(),
+        (8, 2) => day8::part2(input)?.to_string(),
+        (9, 1) => day9::part1(input)?.to_string(),
+        (9, 2) => day9::part2(input)?.to_string(),
+        (10, 2) => day10::part2(input)?,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(answer))
+}
@@ -0,0 +1,154 @@
+use std::{error::Error, fmt};
+
+use nom::{
+    combinator::all_consuming,
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    Finish,
+};
+
+/// nom's `IResult` specialised to string input with [`VerboseError`], so
+/// failures can be turned into a [`ParseError`].
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// A parse failure pointing at the offending spot of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// 1-based column (in characters) within that line
+    pub column: usize,
+    pub expected: String,
+    /// The offending line
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>, text: &str) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            text: text.to_owned(),
+        }
+    }
+
+    /// Builds an error for `token`, which must be a subslice of `text`.
+    pub fn at(line: usize, text: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+        Self::new(line, column_of(text, offset), expected, text)
+    }
+
+    /// Builds an error pointing just past `token`, which must be a subslice
+    /// of `text`.
+    pub fn after(line: usize, text: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize + token.len();
+        Self::new(line, column_of(text, offset), expected, text)
+    }
+
+    /// Builds an error pointing just past the end of `text`, for lines that
+    /// stop before everything expected was found.
+    pub fn at_end(line: usize, text: &str, expected: impl Into<String>) -> Self {
+        Self::new(line, column_of(text, text.len()), expected, text)
+    }
+
+    /// Converts the error of a nom parser that was run on `text`.
+    ///
+    /// The innermost `context` (or expected `char`) decides what is reported
+    /// as expected; without one the innermost nom error kind is used.
+    pub fn from_verbose(line: usize, text: &str, err: VerboseError<&str>) -> Self {
+        let (rest, expected) = err
+            .errors
+            .iter()
+            .find_map(|(rest, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => Some((*rest, ctx.to_string())),
+                VerboseErrorKind::Char(c) => Some((*rest, format!("'{c}'"))),
+                VerboseErrorKind::Nom(_) => None,
+            })
+            .or_else(|| {
+                err.errors.first().map(|(rest, kind)| match kind {
+                    VerboseErrorKind::Nom(ErrorKind::Eof) => (*rest, "end of line".to_owned()),
+                    VerboseErrorKind::Nom(kind) => (*rest, kind.description().to_lowercase()),
+                    _ => unreachable!("handled above"),
+                })
+            })
+            .unwrap_or((text, "valid input".to_owned()));
+
+        Self::new(
+            line,
+            column_of(text, text.len() - rest.len()),
+            expected,
+            text,
+        )
+    }
+}
+
+fn column_of(text: &str, offset: usize) -> usize {
+    text[..offset].chars().count() + 1
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Runs `parser` over the whole of line number `line` (1-based).
+pub fn parse_line<'a, O>(
+    line: usize,
+    text: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    all_consuming(parser)(text)
+        .finish()
+        .map(|(_, o)| o)
+        .map_err(|e| ParseError::from_verbose(line, text, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, error::context, sequence::preceded};
+
+    use super::*;
+
+    fn number(i: &str) -> IResult<'_, u32> {
+        preceded(tag("n="), context("number", nom::character::complete::u32))(i)
+    }
+
+    #[test]
+    fn reports_innermost_context() {
+        let err = parse_line(3, "n=x", number).unwrap_err();
+        assert_eq!(err, ParseError::new(3, 3, "number", "n=x"));
+    }
+
+    #[test]
+    fn reports_trailing_input() {
+        let err = parse_line(1, "n=12 ", number).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "end of line"));
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let text = "é,x";
+        let err = ParseError::at(1, text, &text[3..], "digit");
+        assert_eq!(err.column, 3);
+        assert_eq!(ParseError::after(1, text, &text[..2], "','").column, 2);
+        assert_eq!(ParseError::at_end(1, text, "more").column, 4);
+    }
+
+    #[test]
+    fn points_at_the_column() {
+        let err = ParseError::new(2, 3, "digit", "a-b");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected digit\n  a-b\n    ^"
+        );
+    }
+}
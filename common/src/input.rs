@@ -0,0 +1,88 @@
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+};
+
+use camino::Utf8PathBuf;
+use clap::{Args, Parser};
+
+/// Command-line flags selecting which puzzle input a day runs against.
+///
+/// Without flags the input embedded in the binary is used.
+#[derive(Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// Read the puzzle input from this file, or from stdin when `-`
+    #[arg(long, short, value_name = "PATH", conflicts_with = "example")]
+    pub input: Option<Utf8PathBuf>,
+
+    /// Run against the example from the puzzle description
+    #[arg(long)]
+    pub example: bool,
+}
+
+impl InputArgs {
+    pub fn load(
+        &self,
+        embedded: &'static str,
+        example: Option<&'static str>,
+    ) -> io::Result<Cow<'static, str>> {
+        match &self.input {
+            Some(path) if path == "-" => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input.into())
+            }
+            Some(path) => fs::read_to_string(path)
+                .map(Cow::Owned)
+                .map_err(|e| io::Error::new(e.kind(), format!("reading {path}: {e}"))),
+            None => self.builtin(embedded, example).map(Cow::Borrowed),
+        }
+    }
+
+    /// Like [`InputArgs::load`], but hands out a reader instead of reading
+    /// everything into memory.
+    pub fn open(
+        &self,
+        embedded: &'static str,
+        example: Option<&'static str>,
+    ) -> io::Result<Box<dyn BufRead>> {
+        match &self.input {
+            Some(path) if path == "-" => Ok(Box::new(io::stdin().lock())),
+            Some(path) => File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|e| io::Error::new(e.kind(), format!("opening {path}: {e}"))),
+            None => Ok(Box::new(self.builtin(embedded, example)?.as_bytes())),
+        }
+    }
+
+    fn builtin(
+        &self,
+        embedded: &'static str,
+        example: Option<&'static str>,
+    ) -> io::Result<&'static str> {
+        match example {
+            _ if !self.example => Ok(embedded),
+            Some(example) => Ok(example),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "this day has no example input",
+            )),
+        }
+    }
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Parses the process arguments of a day binary that takes no other flags and
+/// loads the input they select.
+pub fn load_input(
+    embedded: &'static str,
+    example: Option<&'static str>,
+) -> io::Result<Cow<'static, str>> {
+    Cli::parse().input.load(embedded, example)
+}
@@ -0,0 +1,5 @@
+pub mod error;
+pub mod input;
+
+pub use error::{parse_line, IResult, ParseError};
+pub use input::{load_input, InputArgs};
@@ -0,0 +1,141 @@
+use std::cmp::Reverse;
+
+use common::ParseError;
+use itertools::Itertools;
+
+pub mod stream;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = r#"1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+"#;
+
+/// Parses one calorie count per line, with `None` for the blank lines
+/// separating elves.
+pub fn parse(input: &str) -> Result<Vec<Option<u64>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match line.trim() {
+            "" => Ok(None),
+            v => v
+                .parse()
+                .map(Some)
+                .map_err(|_| ParseError::at(i + 1, line, v, "calorie count")),
+        })
+        .collect()
+}
+
+/// The calories carried by one elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    /// Position of the elf in the input, starting at 0
+    pub index: usize,
+    pub calories: u64,
+}
+
+/// Sums up the calories of every elf, in input order.
+pub fn elves(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let elves = parse(input)?
+        .into_iter()
+        .group_by(Option::is_some)
+        .into_iter()
+        .filter(|(is_food, _)| *is_food)
+        .map(|(_, food)| food.flatten().sum::<u64>())
+        .enumerate()
+        .map(|(index, calories)| Elf { index, calories })
+        .collect();
+
+    Ok(elves)
+}
+
+/// The `k` elves carrying the most calories, most first. Ties go to the elf
+/// that comes first in the input.
+pub fn top(elves: &[Elf], k: usize) -> Vec<Elf> {
+    elves
+        .iter()
+        .map(|elf| (Reverse(elf.calories), elf.index))
+        .k_smallest(k)
+        .map(|(Reverse(calories), index)| Elf { index, calories })
+        .collect()
+}
+
+pub fn top_sum(input: &str, k: usize) -> Result<u64, ParseError> {
+    let elves = elves(input)?;
+
+    Ok(top(&elves, k).iter().map(|elf| elf.calories).sum())
+}
+
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+    top_sum(input, 1)
+}
+
+pub fn part2(input: &str) -> Result<u64, ParseError> {
+    top_sum(input, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 24000);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 45000);
+    }
+
+    #[test]
+    fn top_elves() {
+        let elves = elves(EXAMPLE_INPUT).unwrap();
+        assert_eq!(elves.len(), 5);
+
+        let top = top(&elves, 2);
+        assert_eq!(
+            top,
+            [
+                Elf {
+                    index: 3,
+                    calories: 24000
+                },
+                Elf {
+                    index: 2,
+                    calories: 11000
+                },
+            ]
+        );
+        assert_eq!(top_sum(EXAMPLE_INPUT, 10).unwrap(), 55000);
+    }
+
+    #[test]
+    fn skips_repeated_separators() {
+        let elves = elves("\n1\n\n\n2\n3\n").unwrap();
+        assert_eq!(
+            elves.iter().map(|elf| elf.calories).collect::<Vec<_>>(),
+            [1, 5]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_calories() {
+        let err = parse("1000\n2x00\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "calorie count");
+    }
+}
@@ -1,16 +1,53 @@
-use std::cmp::Reverse;
+use clap::Parser;
+use common::InputArgs;
 
-use itertools::Itertools;
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
 
-fn main() {
-    let answer = include_str!("input.txt")
-        .lines()
-        .map(|v| v.parse::<u64>().ok())
-        .batching(|it| it.map_while(|x| x).sum1::<u64>())
-        .map(Reverse)
-        .k_smallest(3)
-        .map(|x| x.0)
-        .sum::<u64>();
+    /// Also list the N elves carrying the most calories
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
 
-    println!("{answer:?}");
+    /// Stream the input and print running statistics instead of the answers
+    #[arg(long)]
+    stream: bool,
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    if cli.stream {
+        let reader = cli.input.open(day1::INPUT, Some(day1::EXAMPLE_INPUT))?;
+        let stats = day1::stream::aggregate(reader, cli.top.unwrap_or(3))?;
+
+        println!("Elves: {}", stats.elves());
+        if let (Some(mean), Some(median), Some(max)) = (stats.mean(), stats.median(), stats.max()) {
+            println!("Mean: {mean:.1}");
+            println!("Median (estimated): {median:.1}");
+            println!("Max: elf #{}: {}", max.index + 1, max.calories);
+        }
+        println!("Top {} sum: {}", stats.top().len(), stats.top_sum());
+        return Ok(());
+    }
+
+    let input = cli.input.load(day1::INPUT, Some(day1::EXAMPLE_INPUT))?;
+
+    println!("Part 1: {}", day1::part1(&input)?);
+    println!("Part 2: {}", day1::part2(&input)?);
+
+    if let Some(k) = cli.top {
+        let elves = day1::elves(&input)?;
+        let top = day1::top(&elves, k);
+
+        println!("Top {} of {} elves:", top.len(), elves.len());
+        for elf in &top {
+            println!("  elf #{}: {}", elf.index + 1, elf.calories);
+        }
+        println!("Sum: {}", top.iter().map(|elf| elf.calories).sum::<u64>());
+    }
+
+    Ok(())
 }
@@ -0,0 +1,356 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+use common::ParseError;
+
+use crate::Elf;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "reading calories: {e}"),
+            StreamError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(e) => Some(e),
+            // displayed as is, so it isn't a further cause
+            StreamError::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+/// Reads elves off a calorie list one at a time, keeping only the current
+/// line in memory.
+///
+/// Blank lines separate elves; any other line that isn't a calorie count is
+/// an error.
+pub struct CalorieStream<R> {
+    reader: R,
+    line: String,
+    line_no: usize,
+    elves: usize,
+}
+
+impl<R: BufRead> CalorieStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_no: 0,
+            elves: 0,
+        }
+    }
+
+    fn next_elf(&mut self) -> Result<Option<Elf>, StreamError> {
+        let mut calories = None;
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+            self.line_no += 1;
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            match line.trim() {
+                "" if calories.is_some() => break,
+                "" => {}
+                v => {
+                    let v = v
+                        .parse::<u64>()
+                        .map_err(|_| ParseError::at(self.line_no, line, v, "calorie count"))?;
+                    calories = Some(calories.unwrap_or(0) + v);
+                }
+            }
+        }
+
+        Ok(calories.map(|calories| {
+            let elf = Elf {
+                index: self.elves,
+                calories,
+            };
+            self.elves += 1;
+            elf
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for CalorieStream<R> {
+    type Item = Result<Elf, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_elf().transpose()
+    }
+}
+
+/// Running statistics over elf totals, in constant memory.
+#[derive(Debug, Clone)]
+pub struct CalorieStats {
+    elves: usize,
+    total: u128,
+    max: Option<Elf>,
+    k: usize,
+    // a min-heap of the `k` best elves, so the weakest is the one evicted
+    top: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+    median: MedianEstimate,
+}
+
+impl CalorieStats {
+    /// Tracks the `k` elves carrying the most calories on top of the totals.
+    pub fn new(k: usize) -> Self {
+        Self {
+            elves: 0,
+            total: 0,
+            max: None,
+            k,
+            top: BinaryHeap::with_capacity(k + 1),
+            median: MedianEstimate::default(),
+        }
+    }
+
+    pub fn push(&mut self, elf: Elf) {
+        self.elves += 1;
+        self.total += u128::from(elf.calories);
+        if self.max.is_none_or(|max| elf.calories > max.calories) {
+            self.max = Some(elf);
+        }
+        self.median.push(elf.calories as f64);
+
+        self.top.push(Reverse((elf.calories, Reverse(elf.index))));
+        if self.top.len() > self.k {
+            self.top.pop();
+        }
+    }
+
+    pub fn elves(&self) -> usize {
+        self.elves
+    }
+
+    pub fn total(&self) -> u128 {
+        self.total
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.elves > 0).then(|| self.total as f64 / self.elves as f64)
+    }
+
+    /// The median elf total. Exact for up to five elves, a P² estimate
+    /// beyond that.
+    pub fn median(&self) -> Option<f64> {
+        self.median.get()
+    }
+
+    /// The first elf carrying the most calories.
+    pub fn max(&self) -> Option<Elf> {
+        self.max
+    }
+
+    /// The `k` elves carrying the most calories, most first.
+    pub fn top(&self) -> Vec<Elf> {
+        let mut top: Vec<_> = self
+            .top
+            .iter()
+            .map(|&Reverse((calories, Reverse(index)))| Elf { index, calories })
+            .collect();
+        top.sort_by_key(|elf| (Reverse(elf.calories), elf.index));
+        top
+    }
+
+    pub fn top_sum(&self) -> u64 {
+        self.top.iter().map(|Reverse((calories, _))| calories).sum()
+    }
+}
+
+/// Streams `reader` into [`CalorieStats`] tracking the top `k` elves.
+pub fn aggregate(reader: impl BufRead, k: usize) -> Result<CalorieStats, StreamError> {
+    let mut stats = CalorieStats::new(k);
+    for elf in CalorieStream::new(reader) {
+        stats.push(elf?);
+    }
+    Ok(stats)
+}
+
+/// Jain and Chlamtac's P² algorithm: estimates the median from five markers
+/// whose heights are adjusted with a piecewise-parabolic fit as samples come
+/// in, without storing the samples.
+#[derive(Debug, Clone, Default)]
+struct MedianEstimate {
+    count: usize,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+}
+
+impl MedianEstimate {
+    const INCREMENTS: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        if self.count <= 5 {
+            self.heights[self.count - 1] = x;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+                self.positions = [1.0, 2.0, 3.0, 4.0, 5.0];
+                self.desired = [1.0, 2.0, 3.0, 4.0, 5.0];
+            }
+            return;
+        }
+
+        let q = &mut self.heights;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (0..4).find(|&i| x < q[i + 1]).unwrap()
+        };
+
+        for i in k + 1..5 {
+            self.positions[i] += 1.0;
+        }
+        for (desired, inc) in self.desired.iter_mut().zip(Self::INCREMENTS) {
+            *desired += inc;
+        }
+
+        for i in 1..4 {
+            let n = &mut self.positions;
+            let d = self.desired[i] - n[i];
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    fn get(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..=4 => {
+                let mut seen = self.heights[..self.count].to_vec();
+                seen.sort_by(f64::total_cmp);
+                let mid = seen.len() / 2;
+                Some(if seen.len().is_multiple_of(2) {
+                    (seen[mid - 1] + seen[mid]) / 2.0
+                } else {
+                    seen[mid]
+                })
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::{elves, top, EXAMPLE_INPUT, INPUT};
+
+    #[test]
+    fn example_stats() {
+        let stats = aggregate(EXAMPLE_INPUT.as_bytes(), 3).unwrap();
+
+        assert_eq!(stats.elves(), 5);
+        assert_eq!(stats.total(), 55000);
+        assert_eq!(stats.mean(), Some(11000.0));
+        assert_eq!(stats.median(), Some(10000.0));
+        assert_eq!(stats.max().map(|elf| elf.index), Some(3));
+        assert_eq!(stats.top_sum(), 45000);
+    }
+
+    #[test]
+    fn matches_in_memory_totals() {
+        let stats = aggregate(INPUT.as_bytes(), 10).unwrap();
+        let elves = elves(INPUT).unwrap();
+
+        assert_eq!(stats.elves(), elves.len());
+        assert_eq!(stats.top(), top(&elves, 10));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let err = aggregate("100\n\n20O\n".as_bytes(), 3).unwrap_err();
+        match err {
+            StreamError::Parse(err) => assert_eq!((err.line, err.column), (3, 1)),
+            StreamError::Io(e) => panic!("unexpected {e}"),
+        }
+    }
+
+    /// Generates `elves` elves carrying 1 to 1000 calories in a single
+    /// item, without ever holding more than one line.
+    struct Generator {
+        elves: u64,
+        line: Vec<u8>,
+    }
+
+    impl Read for Generator {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.line.is_empty() {
+                if self.elves == 0 {
+                    return Ok(0);
+                }
+                self.elves -= 1;
+                self.line = format!("{}\n\n", self.elves % 1000 + 1).into_bytes();
+            }
+            let n = buf.len().min(self.line.len());
+            buf[..n].copy_from_slice(&self.line[..n]);
+            self.line.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn estimates_median_of_large_inputs() {
+        let generator = Generator {
+            elves: 200_000,
+            line: Vec::new(),
+        };
+        let stats = aggregate(io::BufReader::new(generator), 3).unwrap();
+
+        assert_eq!(stats.elves(), 200_000);
+        assert_eq!(stats.max().unwrap().calories, 1000);
+        assert_eq!(stats.top_sum(), 3000);
+        assert_eq!(stats.mean(), Some(500.5));
+        assert!((stats.median().unwrap() - 500.5).abs() < 5.0);
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day1::part1(day1::INPUT).unwrap(), 69281);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day1::part2(day1::INPUT).unwrap(), 201524);
+}
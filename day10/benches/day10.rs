@@ -0,0 +1,12 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    let input = day10::INPUT;
+
+    c.bench_function("day10 part2", |b| b.iter(|| day10::part2(black_box(input))));
+}
+
+criterion_group!(benches, parts);
+criterion_main!(benches);
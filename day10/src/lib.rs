@@ -0,0 +1,195 @@
+use std::{collections::VecDeque, fmt};
+
+use common::{parse_line, IResult, ParseError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{cut, map, value},
+    error::context,
+    sequence::preceded,
+};
+
+pub static EXAMPLE_INPUT: &str = include_str!("example.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    pub fn parse(i: &str) -> IResult<'_, Self> {
+        let noop = tag("noop");
+        let addx = preceded(
+            tag("addx "),
+            cut(context("number", nom::character::complete::i32)),
+        );
+        context(
+            "`noop` or `addx`",
+            alt((value(Self::Noop, noop), map(addx, Self::Addx))),
+        )(i)
+    }
+
+    fn cycles(&self) -> u32 {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
+        }
+    }
+}
+
+pub struct Machine {
+    instructions: VecDeque<Instruction>,
+    current_instruction: Option<(Instruction, u32)>,
+    cycle: u32,
+    x_register: i32,
+    display_lines: Vec<u64>,
+}
+
+impl fmt::Debug for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "cycle={} x={} current={:?} ({} instructions left)",
+            self.cycle,
+            self.x_register,
+            self.current_instruction,
+            self.instructions.len()
+        )?;
+        write!(f, "{self}")
+    }
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.display_lines {
+            for i in 0..40 {
+                let c = if line & cycle_mask(i) > 0 { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Machine {
+    pub fn new(instructions: VecDeque<Instruction>) -> Self {
+        let mut new = Machine {
+            instructions,
+            current_instruction: None,
+            cycle: 0,
+            x_register: 1,
+            display_lines: Vec::new(),
+        };
+        new.decode();
+
+        new
+    }
+
+    fn decode(&mut self) {
+        self.current_instruction = self.instructions.pop_front().map(|ins| (ins, ins.cycles()));
+    }
+
+    pub fn draw(&mut self) {
+        let crt_line = (self.cycle / 40) as usize;
+        if crt_line + 1 > self.display_lines.len() {
+            self.display_lines.push(0);
+        }
+        let crt_line = self.display_lines.get_mut(crt_line).unwrap();
+        let cycle_mask = cycle_mask(self.cycle);
+        let sprite = sprite_value(self.x_register as _);
+        *crt_line |= cycle_mask & sprite;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current_instruction.is_some()
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.current_instruction.is_none() {
+            return false;
+        }
+
+        let (ins, cycles_left) = self.current_instruction.as_mut().unwrap();
+        *cycles_left -= 1;
+        if *cycles_left == 0 {
+            match ins {
+                Instruction::Noop => {}
+                Instruction::Addx(x) => self.x_register += *x,
+            }
+            self.decode();
+        }
+
+        self.cycle += 1;
+        true
+    }
+}
+
+const DISPLAY_MASK: u64 = 0b1111111111111111111111111111111111111111;
+
+fn sprite_value(pos: i32) -> u64 {
+    let model = 0b11100000000000000000000000000000000000000_u64;
+    let shifted;
+    if pos < 0 {
+        (shifted, _) = model.overflowing_shl((-pos).try_into().unwrap());
+    } else {
+        (shifted, _) = model.overflowing_shr(pos.try_into().unwrap());
+    }
+    shifted & DISPLAY_MASK
+}
+
+fn cycle_mask(cycle: u32) -> u64 {
+    (0b1000000000000000000000000000000000000000 >> (cycle % 40)) & DISPLAY_MASK
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn parse_instructions(input: &str) -> Result<VecDeque<Instruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse_line(i + 1, line, Instruction::parse))
+        .collect()
+}
+
+pub fn part2(input: &str) -> Result<String, ParseError> {
+    let mut machine = Machine::new(parse_instructions(input)?);
+
+    // the last instruction completes during the last cycle that is drawn
+    while machine.is_running() {
+        machine.draw();
+        machine.step();
+    }
+    Ok(machine.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            part2(EXAMPLE_INPUT).unwrap(),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_instruction() {
+        let err = parse_instructions("noop\naddx 3\nsubx 3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "`noop` or `addx`");
+    }
+}
@@ -0,0 +1,16 @@
+use pretty_assertions::assert_eq;
+
+#[test]
+fn part2() {
+    assert_eq!(
+        day10::part2(day10::INPUT).unwrap(),
+        "\
+####..##....##..##..###....##.###..####.
+#....#..#....#.#..#.#..#....#.#..#.#....
+###..#.......#.#..#.#..#....#.#..#.###..
+#....#.......#.####.###.....#.###..#....
+#....#..#.#..#.#..#.#....#..#.#.#..#....
+#.....##...##..#..#.#.....##..#..#.####.
+"
+    );
+}
@@ -0,0 +1,346 @@
+use std::{error::Error, fmt, str::FromStr, sync::LazyLock};
+
+use common::ParseError;
+use rules::{Rules, ShapeId};
+
+pub mod optimise;
+pub mod rules;
+pub mod simulate;
+
+static RPS: LazyLock<Rules> = LazyLock::new(Rules::rps);
+
+/// A symbol in the strategy guide that doesn't name what its column holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSymbol {
+    pub symbol: String,
+    pub expected: &'static str,
+}
+
+impl InvalidSymbol {
+    fn new(symbol: impl Into<String>, expected: &'static str) -> Self {
+        InvalidSymbol {
+            symbol: symbol.into(),
+            expected,
+        }
+    }
+}
+
+impl fmt::Display for InvalidSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found `{}`", self.expected, self.symbol)
+    }
+}
+
+impl Error for InvalidSymbol {}
+
+/// Parses a single-character symbol through `TryFrom<char>`.
+fn parse_symbol<T>(s: &str, expected: &'static str) -> Result<T, InvalidSymbol>
+where
+    T: TryFrom<char, Error = InvalidSymbol>,
+{
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => T::try_from(c),
+        _ => Err(InvalidSymbol::new(s, expected)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl Shape {
+    const EXPECTED: &'static str = "opponent shape A, B or C";
+
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    /// This shape in [`Rules::rps`].
+    pub fn id(self) -> ShapeId {
+        ShapeId(self as usize - 1)
+    }
+
+    fn from_id(id: ShapeId) -> Self {
+        Shape::ALL[id.0]
+    }
+
+    /// The shape to play against `self` to end the round with `outcome`.
+    pub fn response_for(self, outcome: Outcome) -> Shape {
+        let response = RPS.response_for(self.id(), outcome);
+        Shape::from_id(response.expect("every outcome is reachable in rock paper scissors"))
+    }
+}
+
+/// The opponent's column: `A`, `B` or `C`.
+impl TryFrom<char> for Shape {
+    type Error = InvalidSymbol;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Shape::Rock),
+            'B' => Ok(Shape::Paper),
+            'C' => Ok(Shape::Scissors),
+            _ => Err(InvalidSymbol::new(c, Shape::EXPECTED)),
+        }
+    }
+}
+
+impl FromStr for Shape {
+    type Err = InvalidSymbol;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_symbol(s, Shape::EXPECTED)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win = 6,
+    Tie = 3,
+    Loss = 0,
+}
+
+impl Outcome {
+    const EXPECTED: &'static str = "outcome X, Y or Z";
+}
+
+impl TryFrom<char> for Outcome {
+    type Error = InvalidSymbol;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Column2::try_from(c)
+            .map(Outcome::from)
+            .map_err(|_| InvalidSymbol::new(c, Outcome::EXPECTED))
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = InvalidSymbol;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_symbol(s, Outcome::EXPECTED)
+    }
+}
+
+/// The second column of the guide, whose meaning depends on the part: a
+/// shape to respond with in part 1, the outcome to aim for in part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column2 {
+    X,
+    Y,
+    Z,
+}
+
+impl Column2 {
+    pub const ALL: [Column2; 3] = [Column2::X, Column2::Y, Column2::Z];
+
+    const EXPECTED: &'static str = "X, Y or Z";
+}
+
+impl TryFrom<char> for Column2 {
+    type Error = InvalidSymbol;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'X' => Ok(Column2::X),
+            'Y' => Ok(Column2::Y),
+            'Z' => Ok(Column2::Z),
+            _ => Err(InvalidSymbol::new(c, Column2::EXPECTED)),
+        }
+    }
+}
+
+impl FromStr for Column2 {
+    type Err = InvalidSymbol;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_symbol(s, Column2::EXPECTED)
+    }
+}
+
+/// The part 1 reading of the column.
+impl From<Column2> for Shape {
+    fn from(c: Column2) -> Self {
+        match c {
+            Column2::X => Shape::Rock,
+            Column2::Y => Shape::Paper,
+            Column2::Z => Shape::Scissors,
+        }
+    }
+}
+
+/// The part 2 reading of the column.
+impl From<Column2> for Outcome {
+    fn from(c: Column2) -> Self {
+        match c {
+            Column2::X => Outcome::Loss,
+            Column2::Y => Outcome::Tie,
+            Column2::Z => Outcome::Win,
+        }
+    }
+}
+
+pub struct Game {
+    pub opponent: Shape,
+    pub response: Shape,
+}
+
+impl Game {
+    pub fn new(opponent: Shape, response: Shape) -> Self {
+        Game { opponent, response }
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        RPS.outcome(self.opponent.id(), self.response.id())
+    }
+
+    pub fn score(&self) -> i32 {
+        self.outcome() as i32 + self.response as i32
+    }
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = r#"
+A Y
+B X
+C Z
+"#;
+
+/// The encrypted strategy guide, parsed once and read either way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyGuide {
+    pub rounds: Vec<(Shape, Column2)>,
+}
+
+impl StrategyGuide {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let rounds = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let mut columns = line.split_whitespace();
+                let opponent = parse_column(i + 1, line, columns.next(), Shape::EXPECTED)?;
+                let column = parse_column(i + 1, line, columns.next(), Column2::EXPECTED)?;
+
+                match columns.next() {
+                    Some(extra) => Err(ParseError::at(i + 1, line, extra, "end of line")),
+                    None => Ok((opponent, column)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(StrategyGuide { rounds })
+    }
+
+    /// Plays the rounds, picking each response from the opponent's shape and
+    /// the second column.
+    pub fn games<'a>(
+        &'a self,
+        respond: impl Fn(Shape, Column2) -> Shape + 'a,
+    ) -> impl Iterator<Item = Game> + 'a {
+        self.rounds
+            .iter()
+            .map(move |&(opponent, column)| Game::new(opponent, respond(opponent, column)))
+    }
+
+    /// The total score when the second column is the shape to play.
+    pub fn score_as_shapes(&self) -> i32 {
+        self.games(|_, column| column.into())
+            .map(|game| game.score())
+            .sum()
+    }
+
+    /// The total score when the second column is the outcome to aim for.
+    pub fn score_as_outcomes(&self) -> i32 {
+        self.games(|opponent, column| opponent.response_for(column.into()))
+            .map(|game| game.score())
+            .sum()
+    }
+}
+
+impl FromStr for StrategyGuide {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StrategyGuide::parse(s)
+    }
+}
+
+fn parse_column<T: FromStr<Err = InvalidSymbol>>(
+    line_no: usize,
+    line: &str,
+    token: Option<&str>,
+    expected: &str,
+) -> Result<T, ParseError> {
+    let token = token.ok_or_else(|| ParseError::at_end(line_no, line, expected))?;
+    token
+        .parse()
+        .map_err(|e: InvalidSymbol| ParseError::at(line_no, line, token, e.expected))
+}
+
+pub fn part1(input: &str) -> Result<i32, ParseError> {
+    Ok(StrategyGuide::parse(input)?.score_as_shapes())
+}
+
+pub fn part2(input: &str) -> Result<i32, ParseError> {
+    Ok(StrategyGuide::parse(input)?.score_as_outcomes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 15);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 12);
+    }
+
+    #[test]
+    fn parses_symbols() {
+        assert_eq!("B".parse(), Ok(Shape::Paper));
+        assert_eq!("Z".parse(), Ok(Outcome::Win));
+        assert_eq!(Column2::try_from('X'), Ok(Column2::X));
+        assert_eq!(
+            "X".parse::<Shape>(),
+            Err(InvalidSymbol::new("X", "opponent shape A, B or C"))
+        );
+        assert!("AB".parse::<Shape>().is_err());
+    }
+
+    #[test]
+    fn parses_guide_once() {
+        let guide = StrategyGuide::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            guide.rounds,
+            [
+                (Shape::Rock, Column2::Y),
+                (Shape::Paper, Column2::X),
+                (Shape::Scissors, Column2::Z)
+            ]
+        );
+        assert_eq!(guide.score_as_shapes(), 15);
+        assert_eq!(guide.score_as_outcomes(), 12);
+    }
+
+    #[test]
+    fn rejects_unknown_column() {
+        let err = part2("A Y\nB W\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "X, Y or Z");
+    }
+
+    #[test]
+    fn rejects_missing_column() {
+        let err = part1("A\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+    }
+}
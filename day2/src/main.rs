@@ -1,116 +1,65 @@
-#[derive(Debug, Clone, Copy)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
-impl Shape {
-    fn from_str(s: &str) -> Self {
-        match s {
-            "A" | "X" => Shape::Rock,
-            "B" | "Y" => Shape::Paper,
-            "C" | "Z" => Shape::Scissors,
-            _ => panic!("Invalid shape"),
-        }
-    }
-
-    fn beats(&self, other: &Self) -> bool {
-        matches!(
-            (self, other),
-            (Shape::Rock, Shape::Scissors)
-                | (Shape::Paper, Shape::Rock)
-                | (Shape::Scissors, Shape::Paper)
-        )
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Outcome {
-    Win = 6,
-    Tie = 3,
-    Loss = 0,
-}
-
-impl Outcome {
-    fn from_str(s: &str) -> Self {
-        match s {
-            "X" => Outcome::Loss,
-            "Y" => Outcome::Tie,
-            "Z" => Outcome::Win,
-            _ => panic!("Invalid shape"),
-        }
-    }
-}
-
-struct Game {
-    opponent: Shape,
-    response: Shape,
+use clap::Parser;
+use common::InputArgs;
+use day2::{
+    simulate::{simulate, StrategySpec},
+    StrategyGuide,
+};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Also report the best and worst possible scores and rank every
+    /// reading of the second column
+    #[arg(long)]
+    optimise: bool,
+
+    /// Instead of scoring the guide, play two strategies against each other:
+    /// rock, paper, scissors, random, frequency or beat-last
+    #[arg(long, num_args = 2, value_names = ["A", "B"])]
+    simulate: Option<Vec<StrategySpec>>,
+
+    /// Number of simulated games
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+    games: u32,
+
+    /// Rounds per simulated game
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    rounds: u32,
+
+    /// Seed for random strategies; the second player gets `seed + 1`
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
-impl Game {
-    fn new(opponent: Shape, response: Shape) -> Self {
-        Game { opponent, response }
-    }
-
-    fn score(&self) -> i32 {
-        let outcome = if self.opponent.beats(&self.response) {
-            Outcome::Loss
-        } else if self.response.beats(&self.opponent) {
-            Outcome::Win
-        } else {
-            Outcome::Tie
-        };
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
 
-        outcome as i32 + self.response as i32
+    let cli = Cli::parse();
+    if let Some(specs) = &cli.simulate {
+        let mut a = specs[0].build(cli.seed);
+        let mut b = specs[1].build(cli.seed.wrapping_add(1));
+        println!("{}", simulate(&mut *a, &mut *b, cli.games, cli.rounds));
+        return Ok(());
     }
-}
 
-fn main() {
-    let input = include_str!("input.txt");
+    let input = cli.input.load(day2::INPUT, Some(day2::EXAMPLE_INPUT))?;
+    let guide = StrategyGuide::parse(&input)?;
 
-    part1(input);
-    part2(input);
-}
-
-fn part1(input: &str) {
-    let mut score: i32 = 0;
-    for line in input.lines().filter(|line| !line.trim().is_empty()) {
-        let mut chars = line.split_whitespace();
-
-        let opponent = Shape::from_str(chars.next().unwrap());
-        let response = Shape::from_str(chars.next().unwrap());
-        let game = Game::new(opponent, response);
-        score += game.score();
-    }
+    println!("Part 1: {}", guide.score_as_shapes());
+    println!("Part 2: {}", guide.score_as_outcomes());
 
-    println!("Part 1: {}", score);
-}
-
-fn part2(input: &str) {
-    let mut score: i32 = 0;
-    for line in input.lines().filter(|line| !line.trim().is_empty()) {
-        let mut chars = line.split_whitespace();
-
-        let opponent = Shape::from_str(chars.next().unwrap());
-
-        let needed_outcome = Outcome::from_str(chars.next().unwrap());
-        let response = match needed_outcome {
-            Outcome::Win => match opponent {
-                Shape::Rock => Shape::Paper,
-                Shape::Paper => Shape::Scissors,
-                Shape::Scissors => Shape::Rock,
-            },
-            Outcome::Tie => opponent,
-            Outcome::Loss => match opponent {
-                Shape::Rock => Shape::Scissors,
-                Shape::Paper => Shape::Rock,
-                Shape::Scissors => Shape::Paper,
-            },
-        };
-
-        let game = Game::new(opponent, response);
-        score += game.score();
+    if cli.optimise {
+        let analysis = guide.analyse();
+        println!("Best possible: {}", analysis.max);
+        println!("Worst possible: {}", analysis.min);
+        for (mapping, score) in &analysis.mappings {
+            println!("  {mapping}: {score}");
+        }
+        let (mapping, score) = analysis.intended();
+        println!("Most likely intended: {mapping} ({score})");
     }
 
-    println!("Part 2: {}", score);
+    Ok(())
 }
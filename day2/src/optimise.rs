@@ -0,0 +1,128 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::{Column2, Game, Shape, StrategyGuide};
+
+/// One way of reading the second column as shapes, as in part 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping(pub [Shape; 3]);
+
+impl Mapping {
+    /// `X` = Rock, `Y` = Paper, `Z` = Scissors, as the elf explained it.
+    pub const PART1: Mapping = Mapping(Shape::ALL);
+
+    /// All six ways to assign a different shape to each of `X`, `Y` and `Z`.
+    pub fn all() -> impl Iterator<Item = Mapping> {
+        Shape::ALL
+            .into_iter()
+            .permutations(3)
+            .map(|shapes| Mapping([shapes[0], shapes[1], shapes[2]]))
+    }
+
+    pub fn apply(&self, column: Column2) -> Shape {
+        self.0[column as usize]
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let assignments = Column2::ALL
+            .iter()
+            .map(|&column| format!("{column:?}={:?}", self.apply(column)))
+            .join(" ");
+        write!(f, "{assignments}")
+    }
+}
+
+/// What the guide could have scored against this opponent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// The best total score, responding perfectly to every round.
+    pub max: i32,
+    /// The worst total score, responding as badly as possible.
+    pub min: i32,
+    /// Every mapping of the second column with its part 1 score, best first.
+    pub mappings: Vec<(Mapping, i32)>,
+}
+
+impl Analysis {
+    /// The mapping the guide's author most likely meant: the one that scores
+    /// best, preferring the part 1 reading on ties.
+    pub fn intended(&self) -> (Mapping, i32) {
+        self.mappings[0]
+    }
+}
+
+impl StrategyGuide {
+    fn best_round(&self, pick: impl Fn(i32, i32) -> i32) -> i32 {
+        self.rounds
+            .iter()
+            .map(|&(opponent, _)| {
+                Shape::ALL
+                    .map(|response| Game::new(opponent, response).score())
+                    .into_iter()
+                    .reduce(&pick)
+                    .unwrap()
+            })
+            .sum()
+    }
+
+    /// The highest score achievable against the opponent's moves.
+    pub fn max_score(&self) -> i32 {
+        self.best_round(i32::max)
+    }
+
+    /// The lowest score achievable against the opponent's moves.
+    pub fn min_score(&self) -> i32 {
+        self.best_round(i32::min)
+    }
+
+    pub fn score_with(&self, mapping: Mapping) -> i32 {
+        self.games(|_, column| mapping.apply(column))
+            .map(|game| game.score())
+            .sum()
+    }
+
+    pub fn analyse(&self) -> Analysis {
+        let mut mappings: Vec<_> = Mapping::all()
+            .map(|mapping| (mapping, self.score_with(mapping)))
+            .collect();
+        // stable, so the part 1 mapping (which comes first) wins ties
+        mappings.sort_by_key(|&(_, score)| -score);
+
+        Analysis {
+            max: self.max_score(),
+            min: self.min_score(),
+            mappings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE_INPUT;
+
+    #[test]
+    fn example_bounds() {
+        let guide = StrategyGuide::parse(EXAMPLE_INPUT).unwrap();
+        let analysis = guide.analyse();
+
+        // paper beats rock, scissors beats paper, rock beats scissors
+        assert_eq!(analysis.max, 8 + 9 + 7);
+        assert_eq!(analysis.min, 3 + 1 + 2);
+        assert_eq!(analysis.mappings.len(), 6);
+        assert!(analysis.mappings.is_sorted_by_key(|&(_, score)| -score));
+    }
+
+    #[test]
+    fn finds_intended_mapping() {
+        let guide = StrategyGuide::parse(EXAMPLE_INPUT).unwrap();
+        let (mapping, score) = guide.analyse().intended();
+
+        assert_eq!(mapping.to_string(), "X=Scissors Y=Paper Z=Rock");
+        assert_eq!(score, 24);
+        assert_eq!(guide.score_with(Mapping::PART1), 15);
+    }
+}
@@ -0,0 +1,284 @@
+use std::{error::Error, fmt};
+
+use common::ParseError;
+
+use crate::Outcome;
+
+/// A shape in some [`Rules`], by its position in the definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShapeId(pub usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    Empty,
+    /// A cyclic game over this even number of shapes, which can't be
+    /// balanced.
+    EvenCycle(usize),
+    DuplicateShape(String),
+    UnknownShape(String),
+    BeatsItself(String),
+    /// Both shapes are listed as beating the other.
+    Contradiction(String, String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::Empty => write!(f, "a game needs at least one shape"),
+            RulesError::EvenCycle(n) => {
+                write!(f, "a balanced game needs an odd number of shapes, not {n}")
+            }
+            RulesError::DuplicateShape(s) => write!(f, "shape `{s}` is defined twice"),
+            RulesError::UnknownShape(s) => write!(f, "unknown shape `{s}`"),
+            RulesError::BeatsItself(s) => write!(f, "shape `{s}` can't beat itself"),
+            RulesError::Contradiction(a, b) => write!(f, "`{a}` and `{b}` both beat each other"),
+        }
+    }
+}
+
+impl Error for RulesError {}
+
+/// A rock-paper-scissors-like game: its shapes and which beats which.
+///
+/// Shapes are worth their 1-based position in the definition, and any two
+/// shapes neither of which beats the other tie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    names: Vec<String>,
+    // beats[a][b] is true when a beats b
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// Builds a game from its shape names and `(winner, loser)` pairs.
+    pub fn new(shapes: &[&str], beats: &[(&str, &str)]) -> Result<Self, RulesError> {
+        if shapes.is_empty() {
+            return Err(RulesError::Empty);
+        }
+
+        let mut rules = Rules {
+            names: Vec::with_capacity(shapes.len()),
+            beats: vec![vec![false; shapes.len()]; shapes.len()],
+        };
+        for &name in shapes {
+            if rules.shape(name).is_some() {
+                return Err(RulesError::DuplicateShape(name.to_string()));
+            }
+            rules.names.push(name.to_string());
+        }
+
+        for &(winner, loser) in beats {
+            let find = |name: &str| {
+                rules
+                    .shape(name)
+                    .ok_or_else(|| RulesError::UnknownShape(name.to_string()))
+            };
+            let (w, l) = (find(winner)?, find(loser)?);
+            if w == l {
+                return Err(RulesError::BeatsItself(winner.to_string()));
+            }
+            if rules.beats(l, w) {
+                return Err(RulesError::Contradiction(
+                    winner.to_string(),
+                    loser.to_string(),
+                ));
+            }
+            rules.beats[w.0][l.0] = true;
+        }
+
+        Ok(rules)
+    }
+
+    /// A balanced game over an odd number of shapes, where every shape beats
+    /// the `(n - 1) / 2` shapes listed just before it, wrapping around.
+    pub fn cyclic(shapes: &[&str]) -> Result<Self, RulesError> {
+        let n = shapes.len();
+        if n == 0 {
+            return Err(RulesError::Empty);
+        }
+        if n.is_multiple_of(2) {
+            return Err(RulesError::EvenCycle(n));
+        }
+        let beats: Vec<_> = (0..n)
+            .flat_map(|w| (1..=(n - 1) / 2).map(move |d| (shapes[w], shapes[(w + n - d) % n])))
+            .collect();
+        Rules::new(shapes, &beats)
+    }
+
+    /// Rock, Paper, Scissors.
+    pub fn rps() -> Self {
+        Rules::cyclic(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    /// Rock, Paper, Scissors, Lizard, Spock.
+    pub fn rpsls() -> Self {
+        Rules::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = ShapeId> {
+        (0..self.len()).map(ShapeId)
+    }
+
+    /// Looks a shape up by name, ignoring case.
+    pub fn shape(&self, name: &str) -> Option<ShapeId> {
+        self.names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))
+            .map(ShapeId)
+    }
+
+    pub fn name(&self, shape: ShapeId) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn shape_score(&self, shape: ShapeId) -> i32 {
+        shape.0 as i32 + 1
+    }
+
+    pub fn beats(&self, a: ShapeId, b: ShapeId) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    /// The outcome of a round for whoever plays `response`.
+    pub fn outcome(&self, opponent: ShapeId, response: ShapeId) -> Outcome {
+        if self.beats(opponent, response) {
+            Outcome::Loss
+        } else if self.beats(response, opponent) {
+            Outcome::Win
+        } else {
+            Outcome::Tie
+        }
+    }
+
+    pub fn score(&self, opponent: ShapeId, response: ShapeId) -> i32 {
+        self.outcome(opponent, response) as i32 + self.shape_score(response)
+    }
+
+    /// The lowest-scoring shape that ends the round with `outcome`, if any.
+    pub fn response_for(&self, opponent: ShapeId, outcome: Outcome) -> Option<ShapeId> {
+        self.shapes()
+            .find(|&response| self.outcome(opponent, response) == outcome)
+    }
+
+    /// Scores a tournament written one round per line as the opponent's
+    /// shape followed by the response, both by name.
+    pub fn score_tournament(&self, input: &str) -> Result<i32, ParseError> {
+        let mut score = 0;
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut columns = line.split_whitespace();
+            let mut next_shape = || {
+                let token = columns
+                    .next()
+                    .ok_or_else(|| ParseError::at_end(i + 1, line, "shape name"))?;
+                self.shape(token)
+                    .ok_or_else(|| ParseError::at(i + 1, line, token, "shape name"))
+            };
+            let opponent = next_shape()?;
+            let response = next_shape()?;
+            if let Some(extra) = columns.next() {
+                return Err(ParseError::at(i + 1, line, extra, "end of line"));
+            }
+
+            score += self.score(opponent, response);
+        }
+        Ok(score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpsls_is_balanced() {
+        let rules = Rules::rpsls();
+        for shape in rules.shapes() {
+            let wins = rules.shapes().filter(|&s| rules.beats(shape, s)).count();
+            assert_eq!(wins, 2, "{}", rules.name(shape));
+        }
+        let [rock, spock] = ["rock", "Spock"].map(|name| rules.shape(name).unwrap());
+        assert_eq!(rules.outcome(rock, spock), Outcome::Win);
+        assert_eq!(rules.score(rock, spock), 6 + 5);
+    }
+
+    #[test]
+    fn cyclic_matches_rpsls() {
+        let cyclic = Rules::cyclic(&["Rock", "Spock", "Paper", "Lizard", "Scissors"]).unwrap();
+        let rpsls = Rules::rpsls();
+        for a in rpsls.shapes() {
+            for b in rpsls.shapes() {
+                let (ca, cb) = (cyclic.shape(rpsls.name(a)), cyclic.shape(rpsls.name(b)));
+                assert_eq!(cyclic.beats(ca.unwrap(), cb.unwrap()), rpsls.beats(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn cyclic_needs_an_odd_number_of_shapes() {
+        assert_eq!(Rules::cyclic(&[]), Err(RulesError::Empty));
+        assert_eq!(
+            Rules::cyclic(&["Rock", "Paper", "Scissors", "Well"]),
+            Err(RulesError::EvenCycle(4))
+        );
+        assert!(Rules::cyclic(&["Rock"]).is_ok());
+    }
+
+    #[test]
+    fn responds_to_every_outcome() {
+        let rules = Rules::rpsls();
+        for opponent in rules.shapes() {
+            for outcome in [Outcome::Win, Outcome::Tie, Outcome::Loss] {
+                let response = rules.response_for(opponent, outcome).unwrap();
+                assert_eq!(rules.outcome(opponent, response), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn scores_variant_tournaments() {
+        let input = "Rock Spock\nLizard paper\nScissors Scissors\n";
+        assert_eq!(Rules::rpsls().score_tournament(input).unwrap(), 11 + 2 + 6);
+
+        let err = Rules::rps().score_tournament(input).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    fn rejects_contradictions() {
+        assert_eq!(
+            Rules::new(&["A", "B"], &[("A", "B"), ("B", "A")]),
+            Err(RulesError::Contradiction("B".into(), "A".into()))
+        );
+        assert_eq!(
+            Rules::new(&["A"], &[("A", "C")]),
+            Err(RulesError::UnknownShape("C".into()))
+        );
+    }
+}
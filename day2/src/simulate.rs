@@ -0,0 +1,375 @@
+use std::{fmt, str::FromStr};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{Game, InvalidSymbol, Outcome, Shape};
+
+/// A player in a simulated tournament.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// The shape to throw this round.
+    fn next_move(&mut self) -> Shape;
+
+    /// Called after every round with what the opponent threw.
+    fn observe(&mut self, _opponent: Shape) {}
+
+    /// Forgets everything learnt about the opponent before a new game.
+    fn reset(&mut self) {}
+}
+
+/// Always throws the same shape.
+pub struct Fixed(pub Shape);
+
+impl Strategy for Fixed {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0)
+    }
+
+    fn next_move(&mut self) -> Shape {
+        self.0
+    }
+}
+
+/// Throws uniformly at random, reproducibly for a given seed.
+pub struct Random {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random (seed {})", self.seed)
+    }
+
+    fn next_move(&mut self) -> Shape {
+        Shape::ALL[self.rng.gen_range(0..Shape::ALL.len())]
+    }
+}
+
+/// Expects the opponent to throw whatever they've thrown most so far, and
+/// beats it.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [u32; 3],
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn next_move(&mut self) -> Shape {
+        // max_by_key keeps the last maximum, so walk backwards to favour Rock
+        let expected = Shape::ALL
+            .into_iter()
+            .rev()
+            .max_by_key(|&shape| self.counts[shape.id().0])
+            .unwrap();
+        expected.response_for(Outcome::Win)
+    }
+
+    fn observe(&mut self, opponent: Shape) {
+        self.counts[opponent.id().0] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+}
+
+/// Throws whatever would have beaten the opponent's last move, starting
+/// with Rock.
+#[derive(Default)]
+pub struct BeatLast {
+    last: Option<Shape>,
+}
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        "beat last".to_string()
+    }
+
+    fn next_move(&mut self) -> Shape {
+        self.last
+            .map_or(Shape::Rock, |last| last.response_for(Outcome::Win))
+    }
+
+    fn observe(&mut self, opponent: Shape) {
+        self.last = Some(opponent);
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// A strategy as named on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategySpec {
+    Fixed(Shape),
+    Random,
+    FrequencyCounter,
+    BeatLast,
+}
+
+impl StrategySpec {
+    /// Builds the strategy, seeding it with `seed` if it is random.
+    pub fn build(self, seed: u64) -> Box<dyn Strategy> {
+        match self {
+            StrategySpec::Fixed(shape) => Box::new(Fixed(shape)),
+            StrategySpec::Random => Box::new(Random::new(seed)),
+            StrategySpec::FrequencyCounter => Box::<FrequencyCounter>::default(),
+            StrategySpec::BeatLast => Box::<BeatLast>::default(),
+        }
+    }
+}
+
+impl FromStr for StrategySpec {
+    type Err = InvalidSymbol;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rock" => Ok(StrategySpec::Fixed(Shape::Rock)),
+            "paper" => Ok(StrategySpec::Fixed(Shape::Paper)),
+            "scissors" => Ok(StrategySpec::Fixed(Shape::Scissors)),
+            "random" => Ok(StrategySpec::Random),
+            "frequency" => Ok(StrategySpec::FrequencyCounter),
+            "beat-last" => Ok(StrategySpec::BeatLast),
+            _ => Err(InvalidSymbol::new(
+                s,
+                "rock, paper, scissors, random, frequency or beat-last",
+            )),
+        }
+    }
+}
+
+/// How one player fared over every game of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    pub name: String,
+    /// The total score of each game, in the order they were played.
+    pub scores: Vec<i32>,
+    pub rounds_won: u64,
+    pub games_won: u32,
+}
+
+impl PlayerStats {
+    fn new(name: String) -> Self {
+        PlayerStats {
+            name,
+            scores: Vec::new(),
+            rounds_won: 0,
+            games_won: 0,
+        }
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().map(|&s| f64::from(s)).sum::<f64>() / self.scores.len() as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .scores
+            .iter()
+            .map(|&s| (f64::from(s) - mean).powi(2))
+            .sum::<f64>()
+            / self.scores.len() as f64;
+        variance.sqrt()
+    }
+
+    pub fn min(&self) -> i32 {
+        self.scores.iter().copied().min().unwrap_or(0)
+    }
+
+    pub fn max(&self) -> i32 {
+        self.scores.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The game score below which `p` percent of games fall, by the
+    /// nearest-rank method.
+    pub fn percentile(&self, p: f64) -> i32 {
+        let mut sorted = self.scores.clone();
+        sorted.sort_unstable();
+        let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+/// The outcome of a [`simulate`] run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub games: u32,
+    pub rounds: u32,
+    pub players: [PlayerStats; 2],
+    pub rounds_tied: u64,
+    pub games_tied: u32,
+}
+
+impl Report {
+    fn total_rounds(&self) -> u64 {
+        u64::from(self.games) * u64::from(self.rounds)
+    }
+
+    /// The share of all rounds the player won.
+    pub fn round_win_rate(&self, player: usize) -> f64 {
+        self.players[player].rounds_won as f64 / self.total_rounds() as f64
+    }
+
+    /// The share of games the player won.
+    pub fn game_win_rate(&self, player: usize) -> f64 {
+        f64::from(self.players[player].games_won) / f64::from(self.games)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games of {} rounds", self.games, self.rounds)?;
+        writeln!(
+            f,
+            "{:<20} {:>9} {:>8} {:>6} {:>6} {:>6} {:>6} {:>7} {:>7}",
+            "strategy", "mean", "std dev", "min", "median", "p90", "max", "rounds", "games"
+        )?;
+        for (i, player) in self.players.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<20} {:>9.1} {:>8.1} {:>6} {:>6} {:>6} {:>6} {:>6.1}% {:>6.1}%",
+                player.name,
+                player.mean(),
+                player.std_dev(),
+                player.min(),
+                player.percentile(50.0),
+                player.percentile(90.0),
+                player.max(),
+                self.round_win_rate(i) * 100.0,
+                self.game_win_rate(i) * 100.0,
+            )?;
+        }
+        write!(
+            f,
+            "ties: {:.1}% of rounds, {:.1}% of games",
+            self.rounds_tied as f64 / self.total_rounds() as f64 * 100.0,
+            f64::from(self.games_tied) / f64::from(self.games) * 100.0,
+        )
+    }
+}
+
+/// Plays `games` games of `rounds` rounds between `a` and `b`, scoring each
+/// round for both players as in part 1.
+///
+/// # Panics
+///
+/// If `games` or `rounds` is 0.
+pub fn simulate(a: &mut dyn Strategy, b: &mut dyn Strategy, games: u32, rounds: u32) -> Report {
+    assert!(games > 0 && rounds > 0, "nothing to simulate");
+
+    let mut report = Report {
+        games,
+        rounds,
+        players: [PlayerStats::new(a.name()), PlayerStats::new(b.name())],
+        rounds_tied: 0,
+        games_tied: 0,
+    };
+
+    for _ in 0..games {
+        a.reset();
+        b.reset();
+
+        let mut scores = [0, 0];
+        for _ in 0..rounds {
+            let (move_a, move_b) = (a.next_move(), b.next_move());
+            let game = Game::new(move_b, move_a);
+            match game.outcome() {
+                Outcome::Win => report.players[0].rounds_won += 1,
+                Outcome::Loss => report.players[1].rounds_won += 1,
+                Outcome::Tie => report.rounds_tied += 1,
+            }
+            scores[0] += game.score();
+            scores[1] += Game::new(move_a, move_b).score();
+
+            a.observe(move_b);
+            b.observe(move_a);
+        }
+
+        match scores[0].cmp(&scores[1]) {
+            std::cmp::Ordering::Greater => report.players[0].games_won += 1,
+            std::cmp::Ordering::Less => report.players[1].games_won += 1,
+            std::cmp::Ordering::Equal => report.games_tied += 1,
+        }
+        for (player, score) in report.players.iter_mut().zip(scores) {
+            player.scores.push(score);
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_strategies_are_deterministic() {
+        let report = simulate(&mut Fixed(Shape::Paper), &mut Fixed(Shape::Rock), 3, 10);
+
+        assert_eq!(report.players[0].scores, [80; 3]);
+        assert_eq!(report.players[1].scores, [10; 3]);
+        assert_eq!(report.round_win_rate(0), 1.0);
+        assert_eq!(report.game_win_rate(1), 0.0);
+    }
+
+    #[test]
+    fn beat_last_beats_fixed() {
+        let report = simulate(
+            &mut BeatLast::default(),
+            &mut Fixed(Shape::Scissors),
+            1,
+            100,
+        );
+
+        // rock happens to beat scissors from the very first round
+        assert_eq!(report.players[0].rounds_won, 100);
+        assert_eq!(report.players[0].scores, [700]);
+    }
+
+    #[test]
+    fn frequency_counter_adapts() {
+        let report = simulate(
+            &mut FrequencyCounter::default(),
+            &mut Fixed(Shape::Paper),
+            1,
+            100,
+        );
+
+        // ties the first round throwing Paper against a presumed Rock, then wins
+        assert_eq!(report.rounds_tied, 1);
+        assert_eq!(report.players[0].rounds_won, 99);
+    }
+
+    #[test]
+    fn random_is_reproducible() {
+        let run = || simulate(&mut Random::new(7), &mut Random::new(8), 50, 100);
+        let report = run();
+
+        assert_eq!(report, run());
+        assert!((report.round_win_rate(0) - 1.0 / 3.0).abs() < 0.02);
+        assert_eq!(report.players[0].scores.len(), 50);
+    }
+
+    #[test]
+    fn parses_specs() {
+        assert_eq!("Beat-Last".parse(), Ok(StrategySpec::BeatLast));
+        assert_eq!("rock".parse(), Ok(StrategySpec::Fixed(Shape::Rock)));
+        assert!("lizard".parse::<StrategySpec>().is_err());
+    }
+}
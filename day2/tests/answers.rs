@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day2::part1(day2::INPUT).unwrap(), 14297);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day2::part2(day2::INPUT).unwrap(), 10498);
+}
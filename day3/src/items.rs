@@ -0,0 +1,183 @@
+use std::{
+    fmt,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign},
+};
+
+/// The priority of an item: a-z are 1 to 26, A-Z are 27 to 52.
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// The item with the given priority.
+pub fn item(priority: u32) -> Option<char> {
+    match priority {
+        1..=26 => char::from_u32('a' as u32 + priority - 1),
+        27..=52 => char::from_u32('A' as u32 + priority - 27),
+        _ => None,
+    }
+}
+
+/// A set of item types, one bit per priority.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    /// Every item type there is.
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    /// Collects the items of `items`, skipping anything that isn't a letter.
+    pub fn from_items(items: &str) -> Self {
+        items
+            .chars()
+            .filter_map(priority)
+            .fold(Self::EMPTY, |set, p| set | Self::single(p))
+    }
+
+    fn single(priority: u32) -> Self {
+        ItemSet(1 << priority)
+    }
+
+    pub fn insert(&mut self, item: char) -> bool {
+        match priority(item) {
+            Some(p) => {
+                let new = !self.contains(item);
+                *self |= Self::single(p);
+                new
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        priority(item).is_some_and(|p| self.0 & (1 << p) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items present in every one of `sets`, or [`ItemSet::ALL`] if there
+    /// are none.
+    pub fn intersection(sets: impl IntoIterator<Item = ItemSet>) -> Self {
+        sets.into_iter().fold(Self::ALL, BitAnd::bitand)
+    }
+
+    /// The priorities of the items in the set, lowest first.
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let p = bits.trailing_zeros();
+                bits &= bits - 1;
+                p
+            })
+        })
+    }
+
+    /// The items in the set, in priority order.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        self.priorities().filter_map(item)
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl fmt::Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.items()).finish()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, rhs: Self) -> Self {
+        ItemSet(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for ItemSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, rhs: Self) -> Self {
+        ItemSet(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ItemSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut set = Self::EMPTY;
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priorities_round_trip() {
+        for p in 1..=52 {
+            assert_eq!(priority(item(p).unwrap()), Some(p));
+        }
+        assert_eq!(priority('p'), Some(16));
+        assert_eq!(priority('L'), Some(38));
+        assert_eq!(priority('1'), None);
+        assert_eq!(ItemSet::ALL.len(), 52);
+    }
+
+    #[test]
+    fn intersects_any_number_of_sets() {
+        let sets = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ]
+        .map(ItemSet::from_items);
+
+        assert_eq!(ItemSet::intersection(sets).items().collect::<String>(), "r");
+        assert_eq!(
+            ItemSet::intersection(sets[..2].iter().copied())
+                .items()
+                .collect::<String>(),
+            "frsFM"
+        );
+        assert_eq!(ItemSet::intersection([]), ItemSet::ALL);
+    }
+
+    #[test]
+    fn inserts_and_formats() {
+        let mut set: ItemSet = "aZb".chars().collect();
+        assert!(!set.insert('a'));
+        assert!(set.insert('c'));
+        assert!(!set.insert('!'));
+        assert_eq!(format!("{set:?}"), "{'a', 'b', 'c', 'Z'}");
+        assert_eq!(set.priority_sum(), 1 + 2 + 3 + 52);
+    }
+}
@@ -0,0 +1,153 @@
+use common::ParseError;
+use items::{priority, ItemSet};
+
+pub mod items;
+pub mod validate;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = r#"
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+"#;
+
+/// Returns the non-empty lines of `input`, checking that every item is a
+/// letter.
+pub fn rucksacks(input: &str) -> Result<Vec<&str>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(
+            |(i, line)| match line.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(pos) => Err(ParseError::at(i + 1, line, &line[pos..], "item a-z or A-Z")),
+                None => Ok(line),
+            },
+        )
+        .collect()
+}
+
+/// The items found in both compartments of each rucksack.
+pub fn shared_items(input: &str) -> Result<Vec<ItemSet>, ParseError> {
+    Ok(rucksacks(input)?
+        .into_iter()
+        .map(|line| {
+            let (first, second) = line.split_at(line.len() / 2);
+            ItemSet::from_items(first) & ItemSet::from_items(second)
+        })
+        .collect())
+}
+
+/// The items carried by every elf of each group of `group_size` elves.
+pub fn badges(input: &str, group_size: usize) -> Result<Vec<ItemSet>, ParseError> {
+    assert!(group_size > 0, "groups need at least one elf");
+
+    let rucksacks = rucksacks(input)?;
+    let short = rucksacks.len() % group_size;
+    if short != 0 {
+        // point just past the last rucksack, which the group stops at
+        let (i, last) = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .last()
+            .unwrap();
+        let missing = group_size - short;
+        let expected = match missing {
+            1 => format!("1 more rucksack for a group of {group_size}"),
+            n => format!("{n} more rucksacks for a group of {group_size}"),
+        };
+        return Err(ParseError::at_end(i + 1, last, expected));
+    }
+
+    Ok(rucksacks
+        .chunks(group_size)
+        .map(|group| ItemSet::intersection(group.iter().map(|r| ItemSet::from_items(r))))
+        .collect())
+}
+
+pub fn part1(input: &str) -> Result<u32, ParseError> {
+    Ok(rucksacks(input)?
+        .into_iter()
+        .zip(shared_items(input)?)
+        .map(|(line, shared)| first_priority(&line[line.len() / 2..], shared))
+        .sum())
+}
+
+pub fn part2(input: &str) -> Result<u32, ParseError> {
+    group_badges(input, 3)
+}
+
+/// The sum of the badge priorities for groups of `group_size` elves.
+pub fn group_badges(input: &str, group_size: usize) -> Result<u32, ParseError> {
+    let badges = badges(input, group_size)?;
+    Ok(rucksacks(input)?
+        .chunks(group_size)
+        .zip(badges)
+        .map(|(group, badge)| first_priority(group[group_size - 1], badge))
+        .sum())
+}
+
+/// The priority of the first of `items` that is in `set`, or 0 if none is.
+///
+/// Only one item counts even when several are shared, as the puzzle
+/// expects exactly one; the validator reports any others.
+fn first_priority(items: &str, set: ItemSet) -> u32 {
+    items
+        .chars()
+        .find(|&item| set.contains(item))
+        .and_then(priority)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 157);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 70);
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        let singles = badges(EXAMPLE_INPUT, 1).unwrap();
+        assert_eq!(singles[0], ItemSet::from_items("vJrwpWtwJgWrhcsFMMfFFhFp"));
+        assert_eq!(badges(EXAMPLE_INPUT, 2).unwrap().len(), 3);
+        assert_eq!(badges(EXAMPLE_INPUT, 6).unwrap(), [ItemSet::EMPTY]);
+    }
+
+    #[test]
+    fn counts_one_shared_item_per_rucksack() {
+        // `a` and `b` are both shared, but only the first of the second
+        // compartment counts
+        assert_eq!(part1("abba\n").unwrap(), 2);
+        assert_eq!(part2("ab\nab\nba\n").unwrap(), 2);
+    }
+
+    #[test]
+    fn rejects_incomplete_groups() {
+        let err = part2("abc\nabd\n\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "abd"));
+        assert_eq!(err.expected, "1 more rucksack for a group of 3");
+
+        let err = group_badges("abc\nabd\nabe\nabf\n", 3).unwrap_err();
+        assert_eq!(err.line, 4);
+        assert_eq!(err.expected, "2 more rucksacks for a group of 3");
+    }
+
+    #[test]
+    fn rejects_non_letter_items() {
+        let err = rucksacks("abcA\nab1B\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -1,69 +1,45 @@
-use std::collections::HashSet;
+use clap::Parser;
+use common::InputArgs;
 
-use itertools::Itertools;
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
 
-fn main() {
-    let input = include_str!("input.txt");
+    /// How many elves share a badge in part 2
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    group_size: u32,
 
-    part1(input);
-    part2(input);
-}
-
-fn part1(input: &str) {
-    let capital_start: usize = b'A'.into();
-    let small_start: usize = b'a'.into();
-
-    let mut sum: usize = 0;
-    for line in input.lines().filter(|l| !l.is_empty()) {
-        let first_half = &line[..line.len() / 2];
-        for char in line[line.len() / 2..].chars() {
-            if first_half.contains(char) {
-                match char {
-                    'a'..='z' => {
-                        sum += char as usize - small_start + 1;
-                    }
-                    'A'..='Z' => {
-                        sum += char as usize - capital_start + 27;
-                    }
-                    _ => {}
-                }
-                break;
-            }
-        }
-    }
+    /// Report every malformed rucksack and group instead of the answers
+    #[arg(long)]
+    validate: bool,
 
-    println!("Part 1: {}", sum);
+    /// Print the validation report as JSON
+    #[arg(long, requires = "validate")]
+    json: bool,
 }
 
-fn part2(input: &str) {
-    let capital_start: usize = b'A'.into();
-    let small_start: usize = b'a'.into();
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
 
-    let mut sum: usize = 0;
-    for mut chunk in input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .chunks(3)
-        .into_iter()
-    {
-        let first_chars: HashSet<_> = chunk.next().unwrap().chars().collect();
-        let second_chars: HashSet<_> = chunk.next().unwrap().chars().collect();
+    let cli = Cli::parse();
+    let input = cli.input.load(day3::INPUT, Some(day3::EXAMPLE_INPUT))?;
 
-        for char in chunk.next().unwrap().chars() {
-            if first_chars.contains(&char) && second_chars.contains(&char) {
-                match char {
-                    'a'..='z' => {
-                        sum += char as usize - small_start + 1;
-                    }
-                    'A'..='Z' => {
-                        sum += char as usize - capital_start + 27;
-                    }
-                    _ => {}
-                }
-                break;
-            }
+    if cli.validate {
+        let report = day3::validate::validate(&input, cli.group_size as usize);
+        if cli.json {
+            println!("{}", report.to_json());
+        } else {
+            println!("{report}");
         }
+        return Ok(());
     }
 
-    println!("Part 2: {}", sum);
+    println!("Part 1: {}", day3::part1(&input)?);
+    println!(
+        "Part 2: {}",
+        day3::group_badges(&input, cli.group_size as usize)?
+    );
+
+    Ok(())
 }
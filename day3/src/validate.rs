@@ -0,0 +1,284 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::items::{priority, ItemSet};
+
+/// Something off about a rucksack or a group, that the puzzle answers
+/// quietly paper over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Anomaly {
+    /// The items can't be split into two equal compartments.
+    OddLength {
+        len: usize,
+    },
+    /// An item that isn't a letter, at a 1-based column.
+    InvalidItem {
+        column: usize,
+        item: char,
+    },
+    NoSharedItem,
+    MultipleSharedItems {
+        items: Vec<char>,
+    },
+    NoBadge,
+    MultipleBadges {
+        items: Vec<char>,
+    },
+    /// The last group is short of elves.
+    IncompleteGroup {
+        size: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::OddLength { len } => write!(f, "odd number of items ({len})"),
+            Anomaly::InvalidItem { column, item } => {
+                write!(f, "invalid item {item:?} at column {column}")
+            }
+            Anomaly::NoSharedItem => write!(f, "no item in both compartments"),
+            Anomaly::MultipleSharedItems { items } => {
+                write!(f, "{} items in both compartments: {items:?}", items.len())
+            }
+            Anomaly::NoBadge => write!(f, "no item carried by the whole group"),
+            Anomaly::MultipleBadges { items } => {
+                write!(f, "{} candidate badges: {items:?}", items.len())
+            }
+            Anomaly::IncompleteGroup { size, expected } => {
+                write!(f, "only {size} of {expected} elves")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RucksackReport {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// Every item found in both compartments.
+    pub shared: Vec<char>,
+    pub anomalies: Vec<Anomaly>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GroupReport {
+    /// The line numbers of the group's rucksacks.
+    pub lines: Vec<usize>,
+    /// Every item carried by all of the group.
+    pub badges: Vec<char>,
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Every anomaly in a list of rucksacks, as groups of `group_size`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub group_size: usize,
+    pub rucksacks: Vec<RucksackReport>,
+    pub groups: Vec<GroupReport>,
+}
+
+impl Report {
+    pub fn anomalies(&self) -> usize {
+        let rucksacks = self.rucksacks.iter().map(|r| r.anomalies.len());
+        let groups = self.groups.iter().map(|g| g.anomalies.len());
+        rucksacks.chain(groups).sum()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.anomalies() == 0
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports serialize to JSON")
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rucksack in &self.rucksacks {
+            for anomaly in &rucksack.anomalies {
+                writeln!(f, "line {}: {anomaly}", rucksack.line)?;
+            }
+        }
+        for (i, group) in self.groups.iter().enumerate() {
+            for anomaly in &group.anomalies {
+                writeln!(f, "group {} (lines {:?}): {anomaly}", i + 1, group.lines)?;
+            }
+        }
+        write!(
+            f,
+            "{} anomalies in {} rucksacks and {} groups",
+            self.anomalies(),
+            self.rucksacks.len(),
+            self.groups.len()
+        )
+    }
+}
+
+/// Checks every non-empty line of `input`, without stopping at the first
+/// problem.
+///
+/// # Panics
+///
+/// If `group_size` is 0.
+pub fn validate(input: &str, group_size: usize) -> Report {
+    assert!(group_size > 0, "groups need at least one elf");
+
+    let lines: Vec<_> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| (i + 1, line))
+        .collect();
+
+    let rucksacks = lines
+        .iter()
+        .map(|&(line_no, line)| check_rucksack(line_no, line))
+        .collect();
+
+    let groups = lines
+        .chunks(group_size)
+        .map(|group| {
+            let mut anomalies = Vec::new();
+            if group.len() < group_size {
+                anomalies.push(Anomaly::IncompleteGroup {
+                    size: group.len(),
+                    expected: group_size,
+                });
+            }
+
+            let badges: Vec<_> =
+                ItemSet::intersection(group.iter().map(|(_, line)| ItemSet::from_items(line)))
+                    .items()
+                    .collect();
+            match badges.len() {
+                0 => anomalies.push(Anomaly::NoBadge),
+                1 => {}
+                _ => anomalies.push(Anomaly::MultipleBadges {
+                    items: badges.clone(),
+                }),
+            }
+
+            GroupReport {
+                lines: group.iter().map(|&(line_no, _)| line_no).collect(),
+                badges,
+                anomalies,
+            }
+        })
+        .collect();
+
+    Report {
+        group_size,
+        rucksacks,
+        groups,
+    }
+}
+
+fn check_rucksack(line_no: usize, line: &str) -> RucksackReport {
+    let mut anomalies: Vec<_> = line
+        .chars()
+        .enumerate()
+        .filter(|&(_, item)| priority(item).is_none())
+        .map(|(i, item)| Anomaly::InvalidItem {
+            column: i + 1,
+            item,
+        })
+        .collect();
+
+    let items: Vec<_> = line.chars().collect();
+    if items.len() % 2 != 0 {
+        anomalies.push(Anomaly::OddLength { len: items.len() });
+    }
+
+    let (first, second) = items.split_at(items.len() / 2);
+    let shared: Vec<_> = (first.iter().copied().collect::<ItemSet>()
+        & second.iter().copied().collect::<ItemSet>())
+    .items()
+    .collect();
+    match shared.len() {
+        0 => anomalies.push(Anomaly::NoSharedItem),
+        1 => {}
+        _ => anomalies.push(Anomaly::MultipleSharedItems {
+            items: shared.clone(),
+        }),
+    }
+
+    RucksackReport {
+        line: line_no,
+        shared,
+        anomalies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE_INPUT;
+
+    #[test]
+    fn example_is_valid() {
+        let report = validate(EXAMPLE_INPUT, 3);
+        assert!(report.is_valid(), "{report}");
+        assert_eq!(report.rucksacks[0].line, 2);
+        assert_eq!(report.groups[1].badges, ['Z']);
+    }
+
+    #[test]
+    fn reports_every_anomaly() {
+        let report = validate("abcab\nab1cad\nxy\n", 2);
+
+        assert_eq!(
+            report.rucksacks[0].anomalies,
+            [
+                Anomaly::OddLength { len: 5 },
+                Anomaly::MultipleSharedItems {
+                    items: vec!['a', 'b']
+                }
+            ]
+        );
+        assert_eq!(
+            report.rucksacks[1].anomalies,
+            [Anomaly::InvalidItem {
+                column: 3,
+                item: '1'
+            }]
+        );
+        assert_eq!(report.rucksacks[2].anomalies, [Anomaly::NoSharedItem]);
+        assert_eq!(
+            report.groups[0].anomalies,
+            [Anomaly::MultipleBadges {
+                items: vec!['a', 'b', 'c']
+            }]
+        );
+        assert_eq!(
+            report.groups[1].anomalies,
+            [
+                Anomaly::IncompleteGroup {
+                    size: 1,
+                    expected: 2
+                },
+                Anomaly::MultipleBadges {
+                    items: vec!['x', 'y']
+                }
+            ]
+        );
+        assert_eq!(report.anomalies(), 7);
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let report = validate("ab1\n", 1);
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(json["group_size"], 1);
+        assert_eq!(json["rucksacks"][0]["line"], 1);
+        assert_eq!(
+            json["rucksacks"][0]["anomalies"][0],
+            serde_json::json!({ "kind": "invalid_item", "column": 3, "item": "1" })
+        );
+    }
+}
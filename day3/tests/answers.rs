@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day3::part1(day3::INPUT).unwrap(), 7863);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day3::part2(day3::INPUT).unwrap(), 2488);
+}
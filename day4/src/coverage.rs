@@ -0,0 +1,137 @@
+use std::ops::RangeInclusive;
+
+use crate::{interval_set::IntervalSet, Group};
+
+/// One elf's sections, and where it came from in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// Index of the elf's group in the input, starting at 0.
+    pub group: usize,
+    /// Index of the elf within its group.
+    pub elf: usize,
+    pub sections: RangeInclusive<u32>,
+}
+
+/// How the assignments of every elf cover the sections, across groups.
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    /// Every assignment, by starting section.
+    assignments: Vec<Assignment>,
+    covered: IntervalSet<u32>,
+    shared: IntervalSet<u32>,
+}
+
+impl Coverage {
+    pub fn new(groups: &[Group]) -> Self {
+        let mut assignments: Vec<_> = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, ranges)| {
+                ranges
+                    .iter()
+                    .enumerate()
+                    .map(move |(elf, sections)| Assignment {
+                        group,
+                        elf,
+                        sections: sections.clone(),
+                    })
+            })
+            .filter(|a| !a.sections.is_empty())
+            .collect();
+        assignments.sort_by_key(|a| (*a.sections.start(), a.group, a.elf));
+
+        let covered = assignments.iter().map(|a| a.sections.clone()).collect();
+
+        // everything that started before an assignment reaches, past its
+        // start, as far as the furthest end seen so far
+        let mut shared = IntervalSet::new();
+        let mut reach: Option<u32> = None;
+        for a in &assignments {
+            if let Some(reach) = reach {
+                shared.insert(*a.sections.start()..=reach.min(*a.sections.end()));
+            }
+            reach = reach.max(Some(*a.sections.end()));
+        }
+
+        Coverage {
+            assignments,
+            covered,
+            shared,
+        }
+    }
+
+    /// The sections at least one elf is assigned to.
+    pub fn covered(&self) -> &IntervalSet<u32> {
+        &self.covered
+    }
+
+    /// The number of sections at least one elf is assigned to.
+    pub fn total(&self) -> u64 {
+        self.covered.len()
+    }
+
+    /// The sections nobody is assigned to, between the lowest and the highest
+    /// assigned ones.
+    pub fn gaps(&self) -> Vec<RangeInclusive<u32>> {
+        self.covered.gaps()
+    }
+
+    /// The sections assigned to more than one elf.
+    pub fn shared(&self) -> &IntervalSet<u32> {
+        &self.shared
+    }
+
+    /// Every pair of assignments from different groups that overlap.
+    pub fn overlapping(&self) -> Vec<(&Assignment, &Assignment)> {
+        let mut overlapping = Vec::new();
+        for (i, a) in self.assignments.iter().enumerate() {
+            // sorted by start, so only the ones starting within `a` can overlap it
+            for b in self.assignments[i + 1..]
+                .iter()
+                .take_while(|b| b.sections.start() <= a.sections.end())
+            {
+                if a.group != b.group {
+                    overlapping.push((a, b));
+                }
+            }
+        }
+        overlapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, EXAMPLE_INPUT};
+
+    #[test]
+    fn example_coverage() {
+        let coverage = Coverage::new(&parse(EXAMPLE_INPUT).unwrap());
+
+        assert_eq!(coverage.covered().ranges(), [2..=9]);
+        assert_eq!(coverage.total(), 8);
+        assert!(coverage.gaps().is_empty());
+        assert_eq!(coverage.shared().ranges(), [2..=8]);
+    }
+
+    #[test]
+    fn finds_gaps_and_shared_sections() {
+        let coverage = Coverage::new(&parse("1-3,5-6\n6-8,12-12\n").unwrap());
+
+        assert_eq!(coverage.gaps(), [4..=4, 9..=11]);
+        assert_eq!(coverage.shared().ranges(), [6..=6]);
+
+        let overlapping = coverage.overlapping();
+        assert_eq!(overlapping.len(), 1);
+        let (a, b) = overlapping[0];
+        assert_eq!(((a.group, a.elf), (b.group, b.elf)), ((0, 1), (1, 0)));
+    }
+
+    #[test]
+    fn ignores_overlaps_within_a_group() {
+        let coverage = Coverage::new(&parse("2-8,3-7\n").unwrap());
+
+        assert_eq!(coverage.shared().ranges(), [3..=7]);
+        assert!(coverage.overlapping().is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use std::ops::RangeInclusive;
+
+/// Integer-like types whose values each have a successor and a predecessor,
+/// standing in for the unstable `std::iter::Step`.
+pub trait Discrete: Ord + Copy {
+    fn succ(self) -> Option<Self>;
+
+    fn pred(self) -> Option<Self>;
+
+    /// The number of steps from `start` up to `end`, saturating at
+    /// `u64::MAX`, or 0 if `end` comes first.
+    fn steps_between(start: Self, end: Self) -> u64;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn steps_between(start: Self, end: Self) -> u64 {
+                let steps = (end as i128 - start as i128).max(0);
+                u64::try_from(steps).unwrap_or(u64::MAX)
+            }
+        }
+    )*};
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Set operations on inclusive ranges of discrete values. A range whose
+/// start is past its end is the empty set.
+// ranges already have an inherent `is_empty`
+#[allow(clippy::len_without_is_empty)]
+pub trait InclusiveRangeExt: Sized {
+    /// The number of values in the range, saturating at `u64::MAX`.
+    ///
+    /// Ranges of 8 and 16-bit integers are also `ExactSizeIterator`s, so
+    /// this needs spelling out as `InclusiveRangeExt::len(&range)` on them.
+    fn len(&self) -> u64;
+
+    /// Whether every value of `other` is also in `self`.
+    fn contains_range(&self, other: &Self) -> bool;
+
+    fn contains_or_is_contained_by(&self, other: &Self) -> bool {
+        self.contains_range(other) || other.contains_range(self)
+    }
+
+    /// Whether the ranges share at least one value.
+    fn overlaps(&self, other: &Self) -> bool;
+
+    /// Whether the ranges don't overlap but one starts right after the other
+    /// ends.
+    fn is_adjacent(&self, other: &Self) -> bool;
+
+    /// The values in both ranges, if any.
+    fn intersection(&self, other: &Self) -> Option<Self>;
+
+    /// The values in either range, if they form a single range, that is if
+    /// the ranges overlap, are adjacent or one of them is empty.
+    fn union(&self, other: &Self) -> Option<Self>;
+
+    /// The values of `self` that aren't in `other`: what is left below
+    /// `other`, and what is left above it. An empty `other` splits nothing,
+    /// so all of `self` goes above it if it starts after `other`'s end, and
+    /// below it otherwise.
+    fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>);
+}
+
+impl<T: Discrete> InclusiveRangeExt for RangeInclusive<T> {
+    fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            T::steps_between(*self.start(), *self.end()).saturating_add(1)
+        }
+    }
+
+    fn contains_range(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start() <= other.start() && other.end() <= self.end())
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    fn is_adjacent(&self, other: &Self) -> bool {
+        let touches = |a: &Self, b: &Self| a.end().succ() == Some(*b.start());
+        !self.is_empty() && !other.is_empty() && (touches(self, other) || touches(other, self))
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let range = *self.start().max(other.start())..=*self.end().min(other.end());
+        (!range.is_empty()).then_some(range)
+    }
+
+    fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            Some(other.clone())
+        } else if other.is_empty() {
+            Some(self.clone())
+        } else if self.overlaps(other) || self.is_adjacent(other) {
+            Some(*self.start().min(other.start())..=*self.end().max(other.end()))
+        } else {
+            None
+        }
+    }
+
+    fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(other) {
+            let rest = (!self.is_empty()).then(|| self.clone());
+            return if self.start() > other.end() {
+                (None, rest)
+            } else {
+                (rest, None)
+            };
+        }
+
+        let below = other
+            .start()
+            .pred()
+            .map(|end| *self.start()..=end)
+            .filter(|range| !range.is_empty());
+        let above = other
+            .end()
+            .succ()
+            .map(|start| start..=*self.end())
+            .filter(|range| !range.is_empty());
+        (below, above)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(range: &RangeInclusive<i32>) -> BTreeSet<i32> {
+        range.clone().collect()
+    }
+
+    /// Whether `set` holds a run of consecutive values, which the empty
+    /// set trivially does.
+    fn is_contiguous(set: &BTreeSet<i32>) -> bool {
+        match (set.first(), set.last()) {
+            (Some(&start), Some(&end)) => set.len() as i32 == end - start + 1,
+            _ => true,
+        }
+    }
+
+    fn range() -> impl Strategy<Value = RangeInclusive<i32>> {
+        // starts past ends are allowed, to cover empty ranges
+        (-5..20, -5..20).prop_map(|(start, end)| start..=end)
+    }
+
+    proptest! {
+        #[test]
+        fn len_counts_values(a in range()) {
+            prop_assert_eq!(a.len(), set(&a).len() as u64);
+        }
+
+        #[test]
+        fn contains_range_is_subset(a in range(), b in range()) {
+            prop_assert_eq!(a.contains_range(&b), set(&b).is_subset(&set(&a)));
+        }
+
+        #[test]
+        fn overlaps_is_symmetric(a in range(), b in range()) {
+            prop_assert_eq!(a.overlaps(&b), !set(&a).is_disjoint(&set(&b)));
+            prop_assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        }
+
+        #[test]
+        fn intersection_matches_sets(a in range(), b in range()) {
+            let expected: BTreeSet<_> = set(&a).intersection(&set(&b)).copied().collect();
+            prop_assert_eq!(a.intersection(&b).map_or_else(BTreeSet::new, |r| set(&r)), expected);
+        }
+
+        #[test]
+        fn union_matches_sets(a in range(), b in range()) {
+            let expected: BTreeSet<_> = set(&a).union(&set(&b)).copied().collect();
+            match a.union(&b) {
+                Some(union) => prop_assert_eq!(set(&union), expected),
+                None => prop_assert!(!is_contiguous(&expected)),
+            }
+        }
+
+        #[test]
+        fn adjacent_ranges_touch(a in range(), b in range()) {
+            let expected = !a.is_empty()
+                && !b.is_empty()
+                && !a.overlaps(&b)
+                && is_contiguous(&set(&a).union(&set(&b)).copied().collect());
+            prop_assert_eq!(a.is_adjacent(&b), expected);
+        }
+
+        #[test]
+        fn difference_matches_sets(a in range(), b in range()) {
+            let expected: BTreeSet<_> = set(&a).difference(&set(&b)).copied().collect();
+            let (below, above) = a.difference(&b);
+
+            let mut actual = BTreeSet::new();
+            for piece in below.iter().chain(&above) {
+                prop_assert!(!piece.is_empty());
+                actual.extend(set(piece));
+            }
+            prop_assert_eq!(actual, expected);
+            if let (Some(below), Some(above)) = (&below, &above) {
+                prop_assert!(below.end() < above.start());
+            }
+            if !b.is_empty() {
+                prop_assert!(below.is_none_or(|below| below.end() < b.start()));
+                prop_assert!(above.is_none_or(|above| above.start() > b.end()));
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_disjoint_differences_on_their_side() {
+        assert_eq!((10..=12).difference(&(1..=3)), (None, Some(10..=12)));
+        assert_eq!((1..=3).difference(&(10..=12)), (Some(1..=3), None));
+    }
+
+    #[test]
+    fn fixes_one_sided_overlap() {
+        // the old overlaps only looked for the other range's endpoints in self
+        assert!((3..=7).overlaps(&(2..=8)));
+        assert!((2..=8).overlaps(&(3..=7)));
+    }
+
+    #[test]
+    fn handles_type_extremes() {
+        assert_eq!((0..=u64::MAX).len(), u64::MAX);
+        assert_eq!(InclusiveRangeExt::len(&(u8::MIN..=u8::MAX)), 256);
+        assert_eq!(
+            (0..=u8::MAX).difference(&(200..=255)),
+            (Some(0..=199), None)
+        );
+        assert!((i8::MIN..=-1).is_adjacent(&(0..=i8::MAX)));
+    }
+}
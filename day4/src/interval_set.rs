@@ -0,0 +1,135 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::interval::{Discrete, InclusiveRangeExt};
+
+/// A set of discrete values, stored as sorted ranges that neither overlap
+/// nor touch.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or
+    /// touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // the ranges wholly before `range`, then the ones it merges with
+        let first = self
+            .ranges
+            .partition_point(|r| r.end() < range.start() && !r.is_adjacent(&range));
+        let last = first
+            + self.ranges[first..]
+                .iter()
+                .take_while(|r| r.union(&range).is_some())
+                .count();
+
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold(range, |merged, r| merged.union(r).unwrap());
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(i).is_some_and(|r| r.contains(&value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set, saturating at `u64::MAX`.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(InclusiveRangeExt::len)
+            .fold(0, u64::saturating_add)
+    }
+
+    /// The ranges making up the set, in order.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    /// The values missing between the lowest and highest values of the set.
+    pub fn gaps(&self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].end().succ().unwrap()..=pair[1].start().pred().unwrap())
+            .collect()
+    }
+}
+
+impl<T: Discrete> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_and_adjacent() {
+        let mut set: IntervalSet<u32> = [6..=8, 2..=4, 10..=12, 5..=5].into_iter().collect();
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(20..=19);
+
+        assert_eq!(set.ranges(), [2..=8, 10..=12]);
+        assert_eq!(set.len(), 10);
+        assert_eq!(set.gaps(), [9..=9]);
+        assert!(set.contains(10) && !set.contains(9));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_btreeset(ranges in prop::collection::vec((0u8..60, 0u8..60), 0..12)) {
+            let ranges: Vec<_> = ranges.into_iter().map(|(a, b)| a..=b).collect();
+            let set: IntervalSet<u8> = ranges.iter().cloned().collect();
+            let expected: BTreeSet<u8> = ranges.into_iter().flatten().collect();
+
+            prop_assert_eq!(set.len(), expected.len() as u64);
+            for v in 0..=60 {
+                prop_assert_eq!(set.contains(v), expected.contains(&v));
+            }
+            for pair in set.ranges().windows(2) {
+                prop_assert!(pair[0].end().succ().unwrap() < *pair[1].start());
+            }
+        }
+    }
+}
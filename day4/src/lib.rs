@@ -0,0 +1,177 @@
+use std::ops::RangeInclusive;
+
+use common::ParseError;
+pub use interval::InclusiveRangeExt;
+
+pub mod coverage;
+pub mod interval;
+pub mod interval_set;
+
+/// The section assignments of a group of elves cleaning together, one or
+/// more per line.
+pub type Group = Vec<RangeInclusive<u32>>;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = r#"
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+"#;
+
+/// What makes a group worth counting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Mode {
+    /// One of the ranges contains all the others
+    ContainsAll,
+    /// Every range overlaps every other one
+    AllOverlap,
+    /// At least two of the ranges overlap
+    AnyOverlap,
+}
+
+impl Mode {
+    pub fn matches(self, group: &[RangeInclusive<u32>]) -> bool {
+        match self {
+            Mode::ContainsAll => {
+                let Some(hull) = hull(group) else {
+                    return true;
+                };
+                group.iter().any(|range| range.contains_range(&hull))
+            }
+            // ranges overlap pairwise exactly when they all share a section
+            Mode::AllOverlap => {
+                let start = group.iter().map(|r| *r.start()).max();
+                let end = group.iter().map(|r| *r.end()).min();
+                group.len() < 2 || (group.iter().all(|r| !r.is_empty()) && start <= end)
+            }
+            Mode::AnyOverlap => {
+                let mut ranges: Vec<_> = group.iter().filter(|r| !r.is_empty()).collect();
+                ranges.sort_by_key(|r| r.start());
+                let mut reach = None;
+                ranges.into_iter().any(|r| {
+                    let overlaps = reach.is_some_and(|reach| r.start() <= reach);
+                    reach = reach.max(Some(r.end()));
+                    overlaps
+                })
+            }
+        }
+    }
+}
+
+/// The smallest range holding every non-empty range of `group`.
+fn hull(group: &[RangeInclusive<u32>]) -> Option<RangeInclusive<u32>> {
+    let mut ranges = group.iter().filter(|r| !r.is_empty());
+    let first = ranges.next()?.clone();
+    Some(ranges.fold(first, |hull, r| {
+        *hull.start().min(r.start())..=*hull.end().max(r.end())
+    }))
+}
+
+/// Counts the groups that match `mode`.
+pub fn count(input: &str, mode: Mode) -> Result<usize, ParseError> {
+    Ok(parse(input)?
+        .iter()
+        .filter(|group| mode.matches(group))
+        .count())
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    count(input, Mode::ContainsAll)
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    count(input, Mode::AnyOverlap)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| to_group(i + 1, line))
+        .collect()
+}
+
+fn to_group(line_no: usize, text: &str) -> Result<Group, ParseError> {
+    text.split(',')
+        .map(|range| to_range(line_no, text, range))
+        .collect()
+}
+
+fn to_range(line_no: usize, text: &str, range: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let number = |n: &str| {
+        n.parse::<u32>()
+            .map_err(|_| ParseError::at(line_no, text, n, "section number"))
+    };
+
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::after(line_no, text, range, "'-'"))?;
+
+    Ok(number(start)?..=number(end)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 4);
+    }
+
+    #[test]
+    fn counts_larger_groups() {
+        let input = "1-9,2-3,4-8\n1-4,3-6,4-9\n1-2,5-6,2-3\n1-2,4-5,7-8\n";
+
+        assert_eq!(count(input, Mode::ContainsAll).unwrap(), 1);
+        assert_eq!(count(input, Mode::AllOverlap).unwrap(), 1);
+        assert_eq!(count(input, Mode::AnyOverlap).unwrap(), 3);
+    }
+
+    #[test]
+    fn counts_single_range_groups() {
+        let input = "1-5\n3-3\n";
+
+        assert_eq!(count(input, Mode::ContainsAll).unwrap(), 2);
+        assert_eq!(count(input, Mode::AllOverlap).unwrap(), 2);
+        assert_eq!(count(input, Mode::AnyOverlap).unwrap(), 0);
+    }
+
+    #[test]
+    fn modes_agree_with_pairwise_checks() {
+        for group in parse(EXAMPLE_INPUT).unwrap() {
+            let (a, b) = (&group[0], &group[1]);
+            assert_eq!(
+                Mode::ContainsAll.matches(&group),
+                a.contains_or_is_contained_by(b)
+            );
+            assert_eq!(Mode::AllOverlap.matches(&group), a.overlaps(b));
+            assert_eq!(Mode::AnyOverlap.matches(&group), a.overlaps(b));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        let err = parse("2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 3, "section number")
+        );
+
+        let err = parse("2-4,68\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 7, "'-'"));
+
+        let err = parse("2-4,6-x\n").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (7, "section number"));
+    }
+}
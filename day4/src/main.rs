@@ -1,73 +1,52 @@
-use std::ops::RangeInclusive;
-
-use itertools::Itertools;
-
-trait InclusiveRangeExt {
-    fn contains_range(&self, other: &Self) -> bool;
+use clap::Parser;
+use common::InputArgs;
+use day4::coverage::Coverage;
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Also report how the assignments cover the sections across all groups
+    #[arg(long)]
+    coverage: bool,
+
+    /// Also count the groups matching this mode
+    #[arg(long, value_enum)]
+    mode: Option<day4::Mode>,
+}
 
-    fn contains_or_is_contained_by(&self, other: &Self) -> bool {
-        self.contains_range(other) || other.contains_range(self)
-    }
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
 
-    fn overlaps(&self, other: &Self) -> bool;
+    let cli = Cli::parse();
+    let input = cli.input.load(day4::INPUT, Some(day4::EXAMPLE_INPUT))?;
 
-    fn overlaps_or_is_overlapped_by(&self, other: &Self) -> bool {
-        self.overlaps(other) || other.overlaps(self)
-    }
-}
+    println!("Part 1: {}", day4::part1(&input)?);
+    println!("Part 2: {}", day4::part2(&input)?);
 
-impl<T> InclusiveRangeExt for RangeInclusive<T>
-where
-    T: PartialOrd,
-{
-    fn contains_range(&self, other: &Self) -> bool {
-        self.contains(other.start()) && self.contains(other.end())
+    if let Some(mode) = cli.mode {
+        println!("{mode:?}: {}", day4::count(&input, mode)?);
     }
 
-    fn overlaps(&self, other: &Self) -> bool {
-        self.contains(other.start()) || self.contains(other.end())
+    if cli.coverage {
+        let coverage = Coverage::new(&day4::parse(&input)?);
+        println!(
+            "Sections covered: {} {:?}",
+            coverage.total(),
+            coverage.covered()
+        );
+        println!("Gaps: {:?}", coverage.gaps());
+        println!(
+            "Assigned more than once: {} {:?}",
+            coverage.shared().len(),
+            coverage.shared()
+        );
+        println!(
+            "Overlapping assignments across groups: {}",
+            coverage.overlapping().len()
+        );
     }
-}
-
-fn main() {
-    let input = include_str!("input.txt");
-
-    part1(input);
-    part2(input);
-}
-
-fn part1(input: &str) {
-    let count = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(to_range_tuple)
-        .filter(|(range1, range2)| range1.contains_or_is_contained_by(range2))
-        .count();
-
-    println!("Part 1: {}", count);
-}
-
-fn part2(input: &str) {
-    let count = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(to_range_tuple)
-        .filter(|(range1, range2)| range1.overlaps_or_is_overlapped_by(range2))
-        .count();
-
-    println!("Part 2: {}", count);
-}
 
-fn to_range_tuple(text: &str) -> (RangeInclusive<u32>, RangeInclusive<u32>) {
-    text.split(',')
-        .map(|range| {
-            range
-                .split('-')
-                .map(|n| n.parse().unwrap())
-                .collect_tuple()
-                .map(|(start, end)| start..=end)
-                .unwrap()
-        })
-        .collect_tuple()
-        .unwrap()
+    Ok(())
 }
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day4::part1(day4::INPUT).unwrap(), 483);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day4::part2(day4::INPUT).unwrap(), 874);
+}
@@ -0,0 +1,235 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use common::InputArgs;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::{Attribute, Color, Print, SetAttribute, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use day5::{crane::CraneSpec, Instruction, MoveEvent, Piles};
+
+/// Plays the rearrangement procedure back in the terminal, one crate at a
+/// time.
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// The crane to watch: 9000 or 9001, optionally followed by `:K` to
+    /// lift at most K crates per grab
+    #[arg(long, value_name = "MODEL[:K]", default_value = "9000")]
+    crane: CraneSpec,
+
+    /// Move as many crates as a pile holds when asked for more, instead of
+    /// failing
+    #[arg(long)]
+    lenient: bool,
+
+    /// Crates moved per second while playing
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    speed: u32,
+}
+
+const HELP: &str = "space: play/pause  s/→: step  +/-: speed  r: reset  q: quit";
+
+struct Player {
+    start: Piles,
+    instructions: Vec<Instruction>,
+    /// Every crate moved, with the index of the instruction moving it.
+    events: Vec<(usize, MoveEvent)>,
+    /// The tallest any pile gets, so the drawing doesn't jump around.
+    height: usize,
+
+    piles: Piles,
+    played: usize,
+    speed: u32,
+    paused: bool,
+}
+
+impl Player {
+    fn new(
+        start: Piles,
+        instructions: Vec<Instruction>,
+        events: Vec<(usize, MoveEvent)>,
+        speed: u32,
+    ) -> Self {
+        let height = events
+            .iter()
+            .map(|(_, e)| e.height + 1)
+            .chain(start.0.iter().map(Vec::len))
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            piles: start.clone(),
+            start,
+            instructions,
+            events,
+            height,
+            played: 0,
+            speed,
+            paused: true,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.played == self.events.len()
+    }
+
+    fn reset(&mut self) {
+        self.piles = self.start.clone();
+        self.played = 0;
+        self.paused = true;
+    }
+
+    fn update_state(&mut self) {
+        let Some((_, event)) = self.events.get(self.played) else {
+            return;
+        };
+        self.piles.replay(event);
+        self.played += 1;
+    }
+
+    fn tick(&self) -> Duration {
+        Duration::from_secs(1) / self.speed
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+
+        let last = self.played.checked_sub(1).map(|i| &self.events[i]);
+        let width = self.piles.column_width();
+        for row in (0..self.height).rev() {
+            for pile in 0..self.piles.0.len() {
+                if pile > 0 {
+                    queue!(out, Print(" "))?;
+                }
+                let cell = self.piles.cell(pile, row, width);
+                if last.is_some_and(|(_, e)| e.to == pile && e.height == row) {
+                    queue!(
+                        out,
+                        SetForegroundColor(Color::Yellow),
+                        SetAttribute(Attribute::Bold),
+                        Print(cell),
+                        SetAttribute(Attribute::Reset),
+                    )?;
+                } else {
+                    queue!(out, Print(cell))?;
+                }
+            }
+            queue!(out, Print("\r\n"))?;
+        }
+        queue!(out, Print(self.piles.footer(width)), Print("\r\n\r\n"))?;
+
+        match last {
+            Some((i, _)) => {
+                let ins = &self.instructions[*i];
+                queue!(
+                    out,
+                    Print(format!("line {}: {ins}", ins.line)),
+                    Print("\r\n")
+                )?;
+            }
+            None => queue!(out, Print("start\r\n"))?,
+        }
+        let state = if self.is_done() {
+            "done"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        queue!(
+            out,
+            Print(format!(
+                "crate {}/{} · {} crates/s · {state}\r\n",
+                self.played,
+                self.events.len(),
+                self.speed
+            )),
+            Print(HELP),
+        )?;
+        out.flush()
+    }
+}
+
+/// Puts the terminal back the way it was, even if the player fails.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    let input = cli.input.load(day5::INPUT, Some(day5::EXAMPLE_INPUT))?;
+    let crane = cli.crane.build();
+
+    // run the whole procedure first so a bad move is reported before the
+    // terminal is taken over
+    let (start, instructions) = day5::parse(&input)?;
+    let mut piles = start.clone();
+    let mut events = Vec::new();
+    for (i, ins) in instructions.iter().enumerate() {
+        day5::step_with(&mut piles, ins, crane.as_ref(), cli.lenient, &mut |e| {
+            events.push((i, e))
+        })?;
+    }
+
+    let mut player = Player::new(start, instructions, events, cli.speed);
+    let _terminal = RawTerminal::enter()?;
+    let mut out = io::stdout();
+    let mut next_tick = Instant::now();
+
+    loop {
+        player.draw(&mut out)?;
+
+        let playing = !player.paused && !player.is_done();
+        let wait = next_tick.saturating_duration_since(Instant::now());
+        if playing && !event::poll(wait)? {
+            player.update_state();
+            next_tick += player.tick();
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char(' ') => {
+                player.paused = !player.paused;
+                next_tick = Instant::now();
+            }
+            KeyCode::Char('s') | KeyCode::Right => player.update_state(),
+            KeyCode::Char('+') | KeyCode::Char('=') => player.speed = (player.speed * 2).min(1000),
+            KeyCode::Char('-') => player.speed = (player.speed / 2).max(1),
+            KeyCode::Char('r') => player.reset(),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    let input = day5::INPUT;
+
+    c.bench_function("day5 part1", |b| b.iter(|| day5::part1(black_box(input))));
+    c.bench_function("day5 part2", |b| b.iter(|| day5::part2(black_box(input))));
+}
+
+criterion_group!(benches, parts);
+criterion_main!(benches);
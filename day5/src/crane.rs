@@ -0,0 +1,229 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Instruction, MoveError, MoveEvent, Piles};
+
+/// A crane model, which decides how the crates of an instruction travel.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Carries out `ins`, leaving `piles` untouched if it can't.
+    fn apply(&self, piles: &mut Piles, ins: &Instruction) -> Result<(), MoveError> {
+        self.apply_with(piles, ins, &mut |_| {})
+    }
+
+    /// Carries out `ins` like [`Crane::apply`], reporting every crate moved
+    /// to `on_move` in the order they land.
+    fn apply_with(
+        &self,
+        piles: &mut Piles,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError>;
+}
+
+/// Moves crates one at a time, reversing their order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn apply_with(
+        &self,
+        piles: &mut Piles,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError> {
+        piles.apply_with(ins, on_move)
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn apply_with(
+        &self,
+        piles: &mut Piles,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError> {
+        piles.apply2_with(ins, on_move)
+    }
+}
+
+/// Another crane that can only lift so many crates per grab, and splits
+/// bigger moves into several.
+#[derive(Debug, Clone, Copy)]
+pub struct Limited<C> {
+    crane: C,
+    capacity: usize,
+}
+
+impl<C: Crane> Limited<C> {
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(crane: C, capacity: usize) -> Self {
+        assert!(capacity > 0, "a crane must lift at least one crate");
+        Limited { crane, capacity }
+    }
+}
+
+impl<C: Crane> Crane for Limited<C> {
+    fn name(&self) -> String {
+        format!("{} lifting at most {}", self.crane.name(), self.capacity)
+    }
+
+    fn apply_with(
+        &self,
+        piles: &mut Piles,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError> {
+        // check the whole move up front so a failure can't leave it half done
+        piles.check(ins)?;
+
+        let mut left = ins.quantity;
+        while left > 0 {
+            let grab = Instruction {
+                quantity: left.min(self.capacity),
+                ..ins.clone()
+            };
+            self.crane.apply_with(piles, &grab, on_move)?;
+            left -= grab.quantity;
+        }
+        Ok(())
+    }
+}
+
+/// A crane as named on the command line: `9000` or `9001`, optionally
+/// followed by `:K` to lift at most `K` crates per grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CraneSpec {
+    pub batch: bool,
+    pub capacity: Option<usize>,
+}
+
+impl CraneSpec {
+    pub fn build(self) -> Box<dyn Crane> {
+        match (self.batch, self.capacity) {
+            (false, None) => Box::new(CrateMover9000),
+            (true, None) => Box::new(CrateMover9001),
+            (false, Some(k)) => Box::new(Limited::new(CrateMover9000, k)),
+            (true, Some(k)) => Box::new(Limited::new(CrateMover9001, k)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCraneSpec(String);
+
+impl fmt::Display for InvalidCraneSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected 9000 or 9001, optionally followed by `:` and a capacity of at least 1, found `{}`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidCraneSpec {}
+
+impl FromStr for CraneSpec {
+    type Err = InvalidCraneSpec;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidCraneSpec(s.to_string());
+
+        let (model, capacity) = match s.split_once(':') {
+            Some((model, capacity)) => {
+                let capacity = capacity.parse().ok().filter(|&k| k > 0);
+                (model, Some(capacity.ok_or_else(invalid)?))
+            }
+            None => (s, None),
+        };
+        let batch = match model {
+            "9000" => false,
+            "9001" => true,
+            _ => return Err(invalid()),
+        };
+
+        Ok(CraneSpec { batch, capacity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{parse, run_cranes, EXAMPLE_INPUT};
+
+    #[test]
+    fn runs_every_crane_on_the_same_piles() {
+        let cranes: Vec<Box<dyn Crane>> = vec![
+            Box::new(CrateMover9000),
+            Box::new(CrateMover9001),
+            Box::new(Limited::new(CrateMover9001, 1)),
+            Box::new(Limited::new(CrateMover9001, 2)),
+        ];
+        let tops = run_cranes(EXAMPLE_INPUT, &cranes, false).unwrap();
+
+        assert_eq!(
+            tops,
+            [
+                ("CrateMover 9000".to_string(), "CMZ".to_string()),
+                ("CrateMover 9001".to_string(), "MCD".to_string()),
+                (
+                    "CrateMover 9001 lifting at most 1".to_string(),
+                    "CMZ".to_string()
+                ),
+                (
+                    "CrateMover 9001 lifting at most 2".to_string(),
+                    "MCZ".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn limited_cranes_split_moves() {
+        let (mut piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let ins = Instruction {
+            quantity: 3,
+            src: 1,
+            dst: 2,
+            line: 6,
+        };
+        Limited::new(CrateMover9001, 2)
+            .apply(&mut piles, &ins)
+            .unwrap();
+
+        assert_eq!(
+            format!("{piles:?}"),
+            "Pile 0: [Z, N]\nPile 1: []\nPile 2: [P, C, D, M]\n"
+        );
+    }
+
+    #[test]
+    fn parses_specs() {
+        assert_eq!(
+            "9001:3".parse(),
+            Ok(CraneSpec {
+                batch: true,
+                capacity: Some(3)
+            })
+        );
+        assert!("9002".parse::<CraneSpec>().is_err());
+        assert!("9000:0".parse::<CraneSpec>().is_err());
+    }
+}
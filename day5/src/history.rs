@@ -0,0 +1,253 @@
+use crate::{crane::Crane, step, Crate, Instruction, MoveError, Piles};
+
+/// What one instruction did: the crates it took off the top of one pile and
+/// the ones it left on top of another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Effect {
+    instruction: Instruction,
+    removed: Vec<Crate>,
+    added: Vec<Crate>,
+}
+
+/// Piles that remember every instruction applied to them, so they can be
+/// rewound and replayed without a crane.
+#[derive(Debug, Clone)]
+pub struct History {
+    piles: Piles,
+    effects: Vec<Effect>,
+    /// How many of `effects` are applied to `piles`; the rest can be redone.
+    position: usize,
+}
+
+impl History {
+    pub fn new(piles: Piles) -> Self {
+        History {
+            piles,
+            effects: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Runs a whole procedure, ending up at its last step.
+    pub fn record(
+        piles: Piles,
+        instructions: &[Instruction],
+        crane: &dyn Crane,
+        lenient: bool,
+    ) -> Result<Self, MoveError> {
+        let mut history = History::new(piles);
+        for ins in instructions {
+            history.apply(ins, crane, lenient)?;
+        }
+        Ok(history)
+    }
+
+    /// The piles as of the current step.
+    pub fn piles(&self) -> &Piles {
+        &self.piles
+    }
+
+    /// The number of instructions applied so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of instructions recorded, applied or not.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// The instruction that led to the current step, if any.
+    pub fn last_instruction(&self) -> Option<&Instruction> {
+        self.position
+            .checked_sub(1)
+            .map(|i| &self.effects[i].instruction)
+    }
+
+    /// Applies a new instruction, forgetting anything that could have been
+    /// redone.
+    pub fn apply(
+        &mut self,
+        ins: &Instruction,
+        crane: &dyn Crane,
+        lenient: bool,
+    ) -> Result<(), MoveError> {
+        // a crane only ever touches the two piles named by the instruction,
+        // taking at most `quantity` crates off the top of one and leaving
+        // them on the other
+        let (src, dst) = (ins.src, ins.dst);
+        let (top, dst_len) = match (self.piles.0.get(src), self.piles.0.get(dst)) {
+            (Some(src), Some(dst)) => (
+                src[src.len().saturating_sub(ins.quantity)..].to_vec(),
+                dst.len(),
+            ),
+            // the crane reports the unknown pile
+            _ => (Vec::new(), 0),
+        };
+        step(&mut self.piles, ins, crane, lenient)?;
+
+        let taken = self.piles.0[dst].len() - dst_len;
+        let effect = Effect {
+            instruction: ins.clone(),
+            removed: top[top.len() - taken..].to_vec(),
+            added: self.piles.0[dst][dst_len..].to_vec(),
+        };
+
+        self.effects.truncate(self.position);
+        self.effects.push(effect);
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Steps back one instruction, returning false if already at the start.
+    pub fn undo(&mut self) -> bool {
+        let Some(effect) = self.position.checked_sub(1).map(|i| &self.effects[i]) else {
+            return false;
+        };
+
+        let ins = &effect.instruction;
+        let dst = &mut self.piles.0[ins.dst];
+        dst.truncate(dst.len() - effect.added.len());
+        self.piles.0[ins.src].extend_from_slice(&effect.removed);
+        self.position -= 1;
+        true
+    }
+
+    /// Steps forward one instruction, returning false if already at the end.
+    pub fn redo(&mut self) -> bool {
+        let Some(effect) = self.effects.get(self.position) else {
+            return false;
+        };
+
+        let ins = &effect.instruction;
+        let src = &mut self.piles.0[ins.src];
+        src.truncate(src.len() - effect.removed.len());
+        self.piles.0[ins.dst].extend_from_slice(&effect.added);
+        self.position += 1;
+        true
+    }
+
+    /// Moves to the state after `step` instructions, or to the last step if
+    /// there aren't that many.
+    pub fn seek(&mut self, step: usize) {
+        let step = step.min(self.len());
+        while self.position > step {
+            self.undo();
+        }
+        while self.position < step {
+            self.redo();
+        }
+    }
+
+    /// The first step at which `crate_` is on top of `pile` (counted from 0),
+    /// with step 0 being the piles before any instruction.
+    pub fn first_on_top(&self, crate_: Crate, pile: usize) -> Option<usize> {
+        let mut replay = self.clone();
+        replay.seek(0);
+        loop {
+            if replay.piles.0.get(pile)?.last() == Some(&crate_) {
+                return Some(replay.position);
+            }
+            if !replay.redo() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001, Limited},
+        parse, EXAMPLE_INPUT,
+    };
+
+    fn example(crane: &dyn Crane) -> (Vec<Piles>, History) {
+        let (mut piles, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        let mut states = vec![piles.clone()];
+        for ins in &instructions {
+            crane.apply(&mut piles, ins).unwrap();
+            states.push(piles.clone());
+        }
+
+        let (piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let history = History::record(piles, &instructions, crane, false).unwrap();
+        (states, history)
+    }
+
+    #[test]
+    fn travels_back_and_forth() {
+        let cranes: [&dyn Crane; 3] = [
+            &CrateMover9000,
+            &CrateMover9001,
+            &Limited::new(CrateMover9001, 2),
+        ];
+        for crane in cranes {
+            let (states, mut history) = example(crane);
+            assert_eq!(history.position(), 4);
+
+            for step in [0, 3, 1, 4, 2, 0] {
+                history.seek(step);
+                assert_eq!(history.piles(), &states[step], "step {step}");
+            }
+            assert!(!history.undo());
+            history.seek(10);
+            assert!(!history.redo());
+            assert_eq!(history.piles(), &states[4]);
+        }
+    }
+
+    #[test]
+    fn new_moves_drop_the_redo_tail() {
+        let (_, mut history) = example(&CrateMover9000);
+        history.seek(1);
+
+        let ins = Instruction {
+            quantity: 1,
+            src: 2,
+            dst: 1,
+            line: 0,
+        };
+        history.apply(&ins, &CrateMover9000, false).unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.last_instruction(), Some(&ins));
+        assert!(!history.redo());
+        assert_eq!(history.piles().tops(), "DP");
+    }
+
+    #[test]
+    fn undoes_clamped_moves() {
+        let (piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let mut history = History::new(piles.clone());
+        let ins = Instruction {
+            quantity: 5,
+            src: 1,
+            dst: 2,
+            line: 0,
+        };
+        history.apply(&ins, &CrateMover9000, true).unwrap();
+        assert_eq!(history.piles().0[2].len(), 4);
+
+        assert!(history.undo());
+        assert_eq!(history.piles(), &piles);
+    }
+
+    #[test]
+    fn finds_when_a_crate_reaches_the_top() {
+        let (_, history) = example(&CrateMover9000);
+
+        assert_eq!(history.first_on_top(Crate::from("D"), 1), Some(0));
+        assert_eq!(history.first_on_top(Crate::from("D"), 0), Some(1));
+        assert_eq!(history.first_on_top(Crate::from("Z"), 2), Some(2));
+        assert_eq!(history.first_on_top(Crate::from("P"), 0), None);
+        assert_eq!(history.first_on_top(Crate::from("P"), 7), None);
+    }
+}
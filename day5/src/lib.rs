@@ -0,0 +1,820 @@
+use std::fmt::{self, Debug, Display};
+
+use common::{parse_line, IResult, ParseError};
+use crane::{Crane, CrateMover9000, CrateMover9001};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, space0, space1},
+    combinator::{cut, eof, map, map_res, recognize},
+    error::context,
+    multi::{many0, separated_list1},
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+pub mod crane;
+pub mod history;
+pub mod solve;
+
+/// A crate, known by the label drawn between its brackets.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Crate(pub String);
+
+impl From<&str> for Crate {
+    fn from(label: &str) -> Self {
+        Crate(label.to_string())
+    }
+}
+
+impl Debug for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub quantity: usize,
+    /// Index of the pile to take crates from, starting at 0.
+    pub src: usize,
+    /// Index of the pile to put crates on, starting at 0.
+    pub dst: usize,
+    /// Where the instruction was read from, starting at 1.
+    pub line: usize,
+}
+
+/// Writes the instruction back in the input's syntax.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity,
+            self.src + 1,
+            self.dst + 1
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveErrorKind {
+    /// Piles are reported numbered from 1, as in the drawing.
+    UnknownPile {
+        pile: usize,
+        piles: usize,
+    },
+    InsufficientCrates {
+        pile: usize,
+        available: usize,
+        requested: usize,
+    },
+    SamePile {
+        pile: usize,
+    },
+}
+
+/// An instruction the crane can't carry out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveError {
+    pub line: usize,
+    pub kind: MoveErrorKind,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            MoveErrorKind::UnknownPile { pile, piles } => {
+                write!(f, "there is no pile {pile}, only {piles}")
+            }
+            MoveErrorKind::InsufficientCrates {
+                pile,
+                available,
+                requested,
+            } => write!(
+                f,
+                "can't move {requested} crates from pile {pile}, which only has {available}"
+            ),
+            MoveErrorKind::SamePile { pile } => {
+                write!(f, "can't move crates from pile {pile} onto itself")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Anything that stops a rearrangement procedure from running.
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Move(MoveError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Move(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+// both variants display as the error they wrap, so there is no further source
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<MoveError> for Error {
+    fn from(e: MoveError) -> Self {
+        Error::Move(e)
+    }
+}
+
+/// A single crate changing piles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveEvent {
+    pub crate_: Crate,
+    /// Index of the pile the crate left, starting at 0.
+    pub from: usize,
+    /// Index of the pile the crate landed on, starting at 0.
+    pub to: usize,
+    /// How many crates are below it once it has landed.
+    pub height: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Piles(pub Vec<Vec<Crate>>);
+
+impl Debug for Piles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pile) in self.0.iter().enumerate() {
+            writeln!(f, "Pile {}: {:?}", i, pile)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the piles the way the puzzle input does, numbered footer included,
+/// with every column as wide as the longest label.
+impl Display for Piles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.column_width();
+        let height = self.0.iter().map(Vec::len).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let cells = (0..self.0.len()).map(|pile| self.cell(pile, row, width));
+            writeln!(f, "{}", cells.format(" "))?;
+        }
+        write!(f, "{}", self.footer(width))
+    }
+}
+
+impl Piles {
+    /// How wide a column of the drawing has to be for the longest label.
+    pub fn column_width(&self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .map(|crate_| crate_.0.chars().count() + 2)
+            .fold(3, usize::max)
+    }
+
+    /// The crate at `row` of `pile` (counted from 0, bottom first) as drawn
+    /// in a column `width` wide, or blanks if there is none there.
+    ///
+    /// Crates are centred like the pile numbers, so each one stays above
+    /// its own number whatever the width of its label.
+    pub fn cell(&self, pile: usize, row: usize, width: usize) -> String {
+        match self.0[pile].get(row) {
+            Some(crate_) => format!("{:^width$}", format!("[{crate_}]")),
+            None => " ".repeat(width),
+        }
+    }
+
+    /// The line of pile numbers ending a drawing with columns `width` wide.
+    pub fn footer(&self, width: usize) -> String {
+        (1..=self.0.len()).map(|n| format!("{n:^width$}")).join(" ")
+    }
+
+    /// Checks that `ins` names two different existing piles and doesn't move
+    /// more crates than there are.
+    pub fn check(&self, ins: &Instruction) -> Result<(), MoveError> {
+        self.clamp(ins).and_then(|clamped| {
+            if clamped.quantity < ins.quantity {
+                Err(MoveError {
+                    line: ins.line,
+                    kind: MoveErrorKind::InsufficientCrates {
+                        pile: ins.src + 1,
+                        available: clamped.quantity,
+                        requested: ins.quantity,
+                    },
+                })
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Checks the piles of `ins` like [`Piles::check`], but moves only as
+    /// many crates as there are rather than failing.
+    pub fn clamp(&self, ins: &Instruction) -> Result<Instruction, MoveError> {
+        let error = |kind| MoveError {
+            line: ins.line,
+            kind,
+        };
+        for pile in [ins.src, ins.dst] {
+            if pile >= self.0.len() {
+                return Err(error(MoveErrorKind::UnknownPile {
+                    pile: pile + 1,
+                    piles: self.0.len(),
+                }));
+            }
+        }
+        if ins.src == ins.dst {
+            return Err(error(MoveErrorKind::SamePile { pile: ins.src + 1 }));
+        }
+
+        Ok(Instruction {
+            quantity: ins.quantity.min(self.0[ins.src].len()),
+            ..ins.clone()
+        })
+    }
+
+    /// Moves crates one at a time.
+    pub fn apply(&mut self, ins: &Instruction) -> Result<(), MoveError> {
+        self.apply_with(ins, &mut |_| {})
+    }
+
+    /// Moves crates one at a time, reporting each of them to `on_move`.
+    pub fn apply_with(
+        &mut self,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError> {
+        self.check(ins)?;
+        for _ in 0..ins.quantity {
+            let crate_ = self.0[ins.src].pop().unwrap();
+            self.0[ins.dst].push(crate_.clone());
+            on_move(MoveEvent {
+                crate_,
+                from: ins.src,
+                to: ins.dst,
+                height: self.0[ins.dst].len() - 1,
+            });
+        }
+        Ok(())
+    }
+
+    /// Moves crates all at once, keeping their order.
+    pub fn apply2(&mut self, ins: &Instruction) -> Result<(), MoveError> {
+        self.apply2_with(ins, &mut |_| {})
+    }
+
+    /// Moves crates all at once, keeping their order, and reports each of
+    /// them to `on_move`, bottom first.
+    pub fn apply2_with(
+        &mut self,
+        ins: &Instruction,
+        on_move: &mut dyn FnMut(MoveEvent),
+    ) -> Result<(), MoveError> {
+        self.check(ins)?;
+        let src = &mut self.0[ins.src];
+        let crates_to_move = src.split_off(src.len() - ins.quantity);
+        let base = self.0[ins.dst].len();
+        for (i, crate_) in crates_to_move.iter().enumerate() {
+            on_move(MoveEvent {
+                crate_: crate_.clone(),
+                from: ins.src,
+                to: ins.dst,
+                height: base + i,
+            });
+        }
+        self.0[ins.dst].extend(crates_to_move);
+        Ok(())
+    }
+
+    /// Carries out a move reported by [`Piles::apply_with`] or
+    /// [`Piles::apply2_with`], so the piles can be replayed a crate at a
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// If the crate isn't on its `from` pile.
+    pub fn replay(&mut self, event: &MoveEvent) {
+        let src = &mut self.0[event.from];
+        let i = src
+            .iter()
+            .rposition(|crate_| *crate_ == event.crate_)
+            .expect("the moved crate is on its pile");
+        let crate_ = src.remove(i);
+
+        let dst = &mut self.0[event.to];
+        dst.insert(event.height.min(dst.len()), crate_);
+    }
+
+    /// The crate on top of each pile, skipping empty piles.
+    pub fn tops(&self) -> String {
+        self.0.iter().filter_map(|p| p.last()).join("")
+    }
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = include_str!("example.txt");
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9000, false)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9001, false)
+}
+
+/// Runs the procedure in `input` with `crane`, and returns the crates on
+/// top.
+///
+/// When `lenient`, instructions moving more crates than a pile holds move
+/// what there is instead of failing.
+pub fn rearrange(input: &str, crane: &dyn Crane, lenient: bool) -> Result<String, Error> {
+    let (mut piles, instructions) = parse(input)?;
+    run(&mut piles, &instructions, crane, lenient)?;
+    Ok(piles.tops())
+}
+
+/// Runs the procedure in `input` with each of `cranes`, starting from the
+/// same piles, and returns every crane's name with the crates on top.
+pub fn run_cranes(
+    input: &str,
+    cranes: &[Box<dyn Crane>],
+    lenient: bool,
+) -> Result<Vec<(String, String)>, Error> {
+    let (piles, instructions) = parse(input)?;
+    cranes
+        .iter()
+        .map(|crane| {
+            let mut piles = piles.clone();
+            run(&mut piles, &instructions, crane.as_ref(), lenient)?;
+            Ok((crane.name(), piles.tops()))
+        })
+        .collect()
+}
+
+fn run(
+    piles: &mut Piles,
+    instructions: &[Instruction],
+    crane: &dyn Crane,
+    lenient: bool,
+) -> Result<(), MoveError> {
+    for ins in instructions {
+        step(piles, ins, crane, lenient)?;
+    }
+    Ok(())
+}
+
+/// Carries out a single instruction with `crane`, clamping its quantity
+/// first when `lenient`.
+pub fn step(
+    piles: &mut Piles,
+    ins: &Instruction,
+    crane: &dyn Crane,
+    lenient: bool,
+) -> Result<(), MoveError> {
+    step_with(piles, ins, crane, lenient, &mut |_| {})
+}
+
+/// Carries out a single instruction like [`step`], reporting every crate
+/// moved to `on_move`.
+pub fn step_with(
+    piles: &mut Piles,
+    ins: &Instruction,
+    crane: &dyn Crane,
+    lenient: bool,
+    on_move: &mut dyn FnMut(MoveEvent),
+) -> Result<(), MoveError> {
+    if lenient {
+        let ins = piles.clamp(ins)?;
+        crane.apply_with(piles, &ins, on_move)
+    } else {
+        crane.apply_with(piles, ins, on_move)
+    }
+}
+
+pub fn parse(input: &str) -> Result<(Piles, Vec<Instruction>), ParseError> {
+    let mut lines = input.lines().enumerate();
+    let piles = read_drawing(input, &mut lines)?;
+
+    // we've consumed the "numbers line" but not the separating line
+    match lines.next() {
+        Some((_, "")) => {}
+        Some((i, line)) => return Err(ParseError::at(i + 1, line, line, "blank line")),
+        None => {
+            return Err(ParseError::new(
+                input.lines().count() + 1,
+                1,
+                "blank line",
+                "",
+            ))
+        }
+    }
+
+    let instructions = lines
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            let ins = parse_line(i + 1, line, parse_instruction)?;
+            Ok(Instruction { line: i + 1, ..ins })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((piles, instructions))
+}
+
+/// Parses the drawing at the start of `input`, ignoring anything after its
+/// pile numbers.
+pub fn parse_drawing(input: &str) -> Result<Piles, ParseError> {
+    read_drawing(input, &mut input.lines().enumerate())
+}
+
+/// Reads lines up to and including the pile numbers ending the drawing.
+///
+/// Each crate belongs to the pile whose number is drawn below it, so lines
+/// may be ragged and labels longer than one character, but every crate has
+/// to sit above exactly one number.
+fn read_drawing<'a>(
+    input: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Piles, ParseError> {
+    let lines = lines.skip_while(|(_, line)| line.is_empty());
+
+    let mut rows = Vec::new();
+    for (i, line) in lines {
+        // the "numbers line" ends the drawing
+        if line.trim_start().starts_with(|c: char| c.is_ascii_digit()) {
+            let numbers = parse_line(i + 1, line, parse_pile_numbers)?;
+            return stack(&rows, i + 1, line, &numbers);
+        }
+        rows.push((i + 1, line, parse_line(i + 1, line, parse_crate_line)?));
+    }
+    Err(ParseError::new(
+        input.lines().count() + 1,
+        1,
+        "pile numbers",
+        "",
+    ))
+}
+
+/// Piles up the crates of `rows`, top row first, over the pile `numbers`
+/// of the footer.
+fn stack(
+    rows: &[(usize, &str, Vec<&str>)],
+    footer_line: usize,
+    footer: &str,
+    numbers: &[&str],
+) -> Result<Piles, ParseError> {
+    for (i, number) in numbers.iter().enumerate() {
+        if number.parse() != Ok(i + 1) {
+            let expected = format!("pile number {}", i + 1);
+            return Err(ParseError::at(footer_line, footer, number, expected));
+        }
+    }
+    let columns: Vec<_> = numbers.iter().map(|n| span(footer, n)).collect();
+
+    let mut piles = vec![Vec::new(); numbers.len()];
+    for (line_no, text, crates) in rows.iter().rev() {
+        for crate_ in crates {
+            let (start, end) = span(text, crate_);
+            let mut below =
+                (0..columns.len()).filter(|&pile| columns[pile].0 < end && start < columns[pile].1);
+            let (Some(pile), None) = (below.next(), below.next()) else {
+                let expected = match numbers.len() {
+                    1 => "crate above pile 1".to_string(),
+                    n => format!("crate above exactly one of piles 1 to {n}"),
+                };
+                return Err(ParseError::at(*line_no, text, crate_, expected));
+            };
+            piles[pile].push(Crate::from(&crate_[1..crate_.len() - 1]));
+        }
+    }
+    Ok(Piles(piles))
+}
+
+/// The columns, in characters, that `token` covers within `text`, end
+/// excluded.
+fn span(text: &str, token: &str) -> (usize, usize) {
+    let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+    let start = text[..offset].chars().count();
+    (start, start + token.chars().count())
+}
+
+/// Parses a bracketed crate, returning it brackets and all.
+fn parse_crate(i: &str) -> IResult<'_, &str> {
+    let label = take_while1(|c: char| !c.is_whitespace() && c != '[' && c != ']');
+    recognize(preceded(
+        char('['),
+        cut(terminated(context("crate label", label), char(']'))),
+    ))(i)
+}
+
+/// Parses the crates of a drawing row, however they are spaced.
+fn parse_crate_line(i: &str) -> IResult<'_, Vec<&str>> {
+    terminated(
+        many0(preceded(space0, parse_crate)),
+        preceded(space0, context("crate or end of line", eof)),
+    )(i)
+}
+
+fn parse_pile_numbers(i: &str) -> IResult<'_, Vec<&str>> {
+    delimited(space0, separated_list1(space1, digit1), space0)(i)
+}
+
+fn parse_number(i: &str) -> IResult<'_, usize> {
+    context("number", map_res(digit1, |s: &str| s.parse::<usize>()))(i)
+}
+
+fn parse_pile_number(i: &str) -> IResult<'_, usize> {
+    context(
+        "pile number",
+        map_res(digit1, |s: &str| match s.parse::<usize>() {
+            Ok(0) => Err("piles are numbered from 1"),
+            Ok(n) => Ok(n - 1),
+            Err(_) => Err("not a number"),
+        }),
+    )(i)
+}
+
+/// Parses an instruction, leaving its line number to the caller.
+fn parse_instruction(i: &str) -> IResult<'_, Instruction> {
+    map(
+        tuple((
+            preceded(context("`move `", tag("move ")), parse_number),
+            preceded(context("` from `", tag(" from ")), parse_pile_number),
+            preceded(context("` to `", tag(" to ")), parse_pile_number),
+        )),
+        |(quantity, src, dst)| Instruction {
+            quantity,
+            src,
+            dst,
+            line: 0,
+        },
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn parses_example_piles() {
+        let (piles, instructions) = parse(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            format!("{piles:?}"),
+            "Pile 0: [Z, N]\nPile 1: [M, C, D]\nPile 2: [P]\n"
+        );
+        assert_eq!(instructions.len(), 4);
+    }
+
+    #[test]
+    fn final_piles() {
+        let (mut piles1, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        let mut piles2 = piles1.clone();
+        for ins in &instructions {
+            piles1.apply(ins).unwrap();
+            piles2.apply2(ins).unwrap();
+        }
+
+        assert_eq!(
+            format!("{piles1:?}"),
+            "Pile 0: [C]\nPile 1: [M]\nPile 2: [P, D, N, Z]\n"
+        );
+        assert_eq!(
+            format!("{piles2:?}"),
+            "Pile 0: [M]\nPile 1: [C]\nPile 2: [P, Z, N, D]\n"
+        );
+    }
+
+    #[test]
+    fn reports_every_crate_moved() {
+        let (mut piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let ins = Instruction {
+            quantity: 2,
+            src: 1,
+            dst: 0,
+            line: 0,
+        };
+        let event = |label: &str, height| MoveEvent {
+            crate_: Crate::from(label),
+            from: 1,
+            to: 0,
+            height,
+        };
+
+        let mut events = Vec::new();
+        piles
+            .clone()
+            .apply_with(&ins, &mut |e| events.push(e))
+            .unwrap();
+        assert_eq!(events, [event("D", 2), event("C", 3)]);
+
+        events.clear();
+        piles.apply2_with(&ins, &mut |e| events.push(e)).unwrap();
+        assert_eq!(events, [event("C", 2), event("D", 3)]);
+    }
+
+    #[test]
+    fn replaying_events_matches_the_cranes() {
+        let (start, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        let cranes: [&dyn Crane; 2] = [&CrateMover9000, &CrateMover9001];
+        for crane in cranes {
+            let (mut piles, mut replayed) = (start.clone(), start.clone());
+            for ins in &instructions {
+                step_with(&mut piles, ins, crane, false, &mut |e| replayed.replay(&e)).unwrap();
+                assert_eq!(replayed, piles);
+            }
+        }
+    }
+
+    #[test]
+    fn renders_the_drawing() {
+        let (piles, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        let (drawing, procedure) = EXAMPLE_INPUT.split_once("\n\n").unwrap();
+
+        assert_eq!(piles.to_string(), drawing);
+        assert_eq!(
+            instructions.iter().map(|ins| format!("{ins}\n")).join(""),
+            procedure
+        );
+    }
+
+    #[test]
+    fn round_trips_every_step() {
+        let (mut piles, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        for ins in &instructions {
+            piles.apply(ins).unwrap();
+            let (parsed, _) = parse(&format!("{piles}\n\n")).unwrap();
+            assert_eq!(parsed, piles);
+        }
+
+        for len in 1..=12 {
+            let long = Crate("L".repeat(len));
+            let mixed = Piles(vec![
+                vec![Crate::from("A")],
+                vec![long, Crate::from("B")],
+                vec![Crate::from("CD")],
+            ]);
+            assert_eq!(parse(&format!("{mixed}\n\n")).unwrap().0, mixed, "{len}");
+        }
+
+        let empty = Piles(vec![Vec::new(); 12]);
+        assert_eq!(empty.to_string().len(), 12 * 4 - 1);
+        assert_eq!(parse(&format!("{empty}\n\n")).unwrap().0, empty);
+    }
+
+    #[test]
+    fn parses_ragged_lines() {
+        let trimmed = EXAMPLE_INPUT
+            .lines()
+            .map(str::trim_end)
+            .map(|line| format!("{line}\n"))
+            .join("");
+
+        assert_eq!(parse(&trimmed).unwrap(), parse(EXAMPLE_INPUT).unwrap());
+    }
+
+    #[test]
+    fn parses_long_labels() {
+        let drawing = "[AB]\n[C]  [DEF]\n 1    2   \n";
+        let piles = parse_drawing(drawing).unwrap();
+
+        assert_eq!(format!("{piles:?}"), "Pile 0: [C, AB]\nPile 1: [DEF]\n");
+        assert_eq!(piles.to_string(), "[AB]       \n [C]  [DEF]\n  1     2  ");
+        assert_eq!(parse_drawing(&piles.to_string()).unwrap(), piles);
+
+        let mixed = Piles(vec![vec![Crate::from("A")], vec![Crate::from("LONGLABEL")]]);
+        assert_eq!(
+            mixed.to_string(),
+            "    [A]     [LONGLABEL]\n     1           2     "
+        );
+        assert_eq!(parse_drawing(&mixed.to_string()).unwrap(), mixed);
+    }
+
+    #[test]
+    fn rejects_drawings_that_disagree_with_their_footer() {
+        let err = parse_drawing("[A] [B]\n 1   3 \n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.expected, "pile number 2");
+
+        let err = parse_drawing("[A] [B] [C]\n 1   2 \n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 9));
+        assert_eq!(err.expected, "crate above exactly one of piles 1 to 2");
+
+        let err = parse_drawing("[AB]\n 1 2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = parse_drawing("[A] [B\n 1   2 \n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 7, "']'"));
+
+        let err = parse_drawing("[A] B\n 1   2 \n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 5, "crate or end of line")
+        );
+    }
+
+    #[test]
+    fn rejects_impossible_moves() {
+        let (piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let ins = |quantity, src, dst| Instruction {
+            quantity,
+            src,
+            dst,
+            line: 9,
+        };
+
+        assert_eq!(
+            piles.check(&ins(1, 0, 3)).unwrap_err().kind,
+            MoveErrorKind::UnknownPile { pile: 4, piles: 3 }
+        );
+        assert_eq!(
+            piles.check(&ins(1, 1, 1)).unwrap_err().kind,
+            MoveErrorKind::SamePile { pile: 2 }
+        );
+        let err = piles.check(&ins(2, 2, 0)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 9: can't move 2 crates from pile 3, which only has 1"
+        );
+    }
+
+    #[test]
+    fn failed_moves_leave_piles_alone() {
+        let (mut piles, _) = parse(EXAMPLE_INPUT).unwrap();
+        let before = piles.clone();
+        let ins = Instruction {
+            quantity: 4,
+            src: 1,
+            dst: 0,
+            line: 6,
+        };
+
+        assert!(piles.apply(&ins).is_err());
+        assert!(piles.apply2(&ins).is_err());
+        assert_eq!(piles, before);
+    }
+
+    #[test]
+    fn lenient_mode_clamps_quantities() {
+        let input = EXAMPLE_INPUT.replace("move 3 from 1 to 3", "move 5 from 1 to 3");
+
+        match part1(&input).unwrap_err() {
+            Error::Move(err) => assert_eq!(err.line, 7),
+            Error::Parse(err) => panic!("unexpected {err}"),
+        }
+        assert_eq!(rearrange(&input, &CrateMover9000, true).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn skips_blank_lines_in_the_procedure() {
+        let input = format!(
+            "{}\n",
+            EXAMPLE_INPUT.replace("move 3 from 1", "\nmove 3 from 1")
+        );
+        let (piles, instructions) = parse(&input).unwrap();
+
+        assert_eq!(piles, parse(EXAMPLE_INPUT).unwrap().0);
+        assert_eq!(
+            instructions.iter().map(|ins| ins.line).collect::<Vec<_>>(),
+            [6, 8, 9, 10]
+        );
+        assert_eq!(part1(&input).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn rejects_malformed_instruction() {
+        let input = EXAMPLE_INPUT.replace("move 3 from 1", "move 3 form 1");
+        let err = parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (7, 7));
+        assert_eq!(err.expected, "` from `");
+    }
+}
@@ -1,159 +1,135 @@
-use std::fmt::{self, Debug, Display};
-
-use itertools::Itertools;
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take, take_while1},
-    character::complete::{digit0, digit1},
-    combinator::{all_consuming, map, map_res, opt},
-    multi::separated_list1,
-    sequence::{delimited, preceded, tuple},
-    Finish, IResult,
+use std::fs;
+
+use camino::Utf8PathBuf;
+use clap::Parser;
+use common::InputArgs;
+use day5::{
+    crane::{Crane, CraneSpec, CrateMover9000, CrateMover9001},
+    history::History,
+    solve::solve,
+    Crate,
 };
 
-#[derive(Clone, Copy)]
-struct Crate(char);
-
-impl Debug for Crate {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
-    }
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Move as many crates as a pile holds when asked for more, instead of
+    /// failing
+    #[arg(long)]
+    lenient: bool,
+
+    /// Run the procedure with these cranes instead of the two puzzle ones:
+    /// 9000 or 9001, optionally followed by `:K` to lift at most K crates
+    /// per grab
+    #[arg(long = "crane", value_name = "MODEL[:K]")]
+    cranes: Vec<CraneSpec>,
+
+    /// Print the drawing after every instruction, moved by the first crane
+    /// given or the CrateMover 9000
+    #[arg(long)]
+    dump: bool,
+
+    /// Print the drawing after this many instructions, moved like `--dump`
+    #[arg(long, value_name = "N")]
+    step: Option<usize>,
+
+    /// Find the first step at which crate X is on top of pile K, moved like
+    /// `--dump`
+    #[arg(long, value_name = "X:K", value_parser = parse_top_query)]
+    first_on_top: Option<(String, usize)>,
+
+    /// Print a shortest procedure taking the first crane given, or the
+    /// CrateMover 9000, from the input's drawing to the one in this file
+    #[arg(long, value_name = "GOAL")]
+    solve: Option<Utf8PathBuf>,
+
+    /// How many arrangements `--solve` may try before giving up
+    #[arg(long, default_value_t = 1_000_000)]
+    max_states: usize,
 }
 
-impl Display for Crate {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+fn parse_top_query(s: &str) -> Result<(String, usize), String> {
+    let invalid = || format!("expected a crate label and a pile number like `D:3`, found `{s}`");
+    let (label, pile) = s.rsplit_once(':').ok_or_else(invalid)?;
+
+    match pile.parse() {
+        Ok(pile) if pile > 0 && !label.is_empty() => Ok((label.to_string(), pile)),
+        _ => Err(invalid()),
     }
 }
 
-#[derive(Debug)]
-struct Instruction {
-    quantity: usize,
-    src: usize,
-    dst: usize,
-}
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
 
-#[derive(Clone)]
-struct Piles(Vec<Vec<Crate>>);
+    let cli = Cli::parse();
+    let input = cli.input.load(day5::INPUT, Some(day5::EXAMPLE_INPUT))?;
 
-impl Debug for Piles {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, pile) in self.0.iter().enumerate() {
-            writeln!(f, "Pile {}: {:?}", i, pile)?;
-        }
-        Ok(())
-    }
-}
+    let crane = cli.cranes.first().map_or_else(
+        || Box::new(CrateMover9000) as Box<dyn Crane>,
+        |spec| spec.build(),
+    );
+
+    if let Some(path) = &cli.solve {
+        let (start, _) = day5::parse(&input)?;
+        let goal = day5::parse_drawing(&fs::read_to_string(path)?)?;
+        let procedure = solve(&start, &goal, crane.as_ref(), cli.max_states)?;
 
-impl Piles {
-    fn apply(&mut self, ins: &Instruction) {
-        for _ in 0..ins.quantity {
-            let crate_ = self.0[ins.src].pop().unwrap();
-            self.0[ins.dst].push(crate_);
+        // a complete input, ready to be fed back in
+        println!("{start}\n");
+        for ins in &procedure {
+            println!("{ins}");
         }
+        return Ok(());
     }
 
-    fn apply2(&mut self, ins: &Instruction) {
-        let mut crates_to_move = Vec::new();
-        for _ in 0..ins.quantity {
-            let crate_ = self.0[ins.src].pop().unwrap();
-            crates_to_move.push(crate_);
+    if cli.step.is_some() || cli.first_on_top.is_some() {
+        let (piles, instructions) = day5::parse(&input)?;
+        let mut history = History::record(piles, &instructions, crane.as_ref(), cli.lenient)?;
+
+        if let Some(step) = cli.step {
+            history.seek(step);
+            match history.last_instruction() {
+                Some(ins) => println!("Step {} (line {}: {ins})", history.position(), ins.line),
+                None => println!("Step 0"),
+            }
+            println!("{}\n", history.piles());
         }
-
-        for crate_ in crates_to_move.into_iter().rev() {
-            self.0[ins.dst].push(crate_);
+        if let Some((label, pile)) = &cli.first_on_top {
+            match history.first_on_top(Crate::from(label.as_str()), pile - 1) {
+                Some(step) => println!("[{label}] first tops pile {pile} at step {step}"),
+                None => println!("[{label}] never tops pile {pile}"),
+            }
         }
+        return Ok(());
     }
-}
-
-fn main() {
-    let input = include_str!("input.txt");
-
-    part1(input);
-}
 
-fn part1(input: &str) {
-    let mut lines = input.lines().skip_while(|line| line.is_empty());
-
-    let crate_lines: Vec<_> = lines
-        .by_ref()
-        .map_while(|line| {
-            all_consuming(parse_crate_line)(line)
-                .finish()
-                .ok()
-                .map(|(_, line)| line)
-        })
-        .collect();
-    let mut piles1 = Piles(transpose_rev(crate_lines));
-    let mut piles2 = piles1.clone();
-    println!("{piles1:?}");
-
-    // we've consumed the "numbers line" but not the separating line
-    assert!(lines.next().unwrap().is_empty());
-
-    for i in lines.map(|line| all_consuming(parse_instruction)(line).finish().unwrap().1) {
-        piles1.apply(&i);
-        piles2.apply2(&i);
+    if cli.dump {
+        let (mut piles, instructions) = day5::parse(&input)?;
+        println!("{piles}\n");
+        for ins in &instructions {
+            day5::step(&mut piles, ins, crane.as_ref(), cli.lenient)?;
+            println!("{ins}\n\n{piles}\n");
+        }
+        return Ok(());
     }
 
-    println!(
-        "Part 1: {:?}",
-        piles1.0.iter().map(|p| p.last().unwrap()).join("")
-    );
-    println!(
-        "Part 2: {:?}",
-        piles2.0.iter().map(|p| p.last().unwrap()).join("")
-    );
-}
-
-fn transpose_rev<T>(v: Vec<Vec<Option<T>>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .rev()
-                .filter_map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
-}
-
-fn parse_crate(i: &str) -> IResult<&str, Crate> {
-    let first_char = |s: &str| Crate(s.chars().next().unwrap());
-    let f = delimited(tag("["), take(1_usize), tag("]"));
-    map(f, first_char)(i)
-}
-
-fn parse_hole(i: &str) -> IResult<&str, ()> {
-    map(tag("   "), drop)(i)
-}
-
-fn parse_crate_or_hole(i: &str) -> IResult<&str, Option<Crate>> {
-    alt((map(parse_crate, Some), map(parse_hole, |_| None)))(i)
-}
-
-fn parse_crate_line(i: &str) -> IResult<&str, Vec<Option<Crate>>> {
-    separated_list1(tag(" "), parse_crate_or_hole)(i)
-}
-
-fn parse_number(i: &str) -> IResult<&str, usize> {
-    map_res(digit1, |s: &str| s.parse::<usize>())(i)
-}
-
-fn parse_pile_number(i: &str) -> IResult<&str, usize> {
-    map(parse_number, |i| i - 1)(i)
-}
+    if cli.cranes.is_empty() {
+        println!(
+            "Part 1: {:?}",
+            day5::rearrange(&input, &CrateMover9000, cli.lenient)?
+        );
+        println!(
+            "Part 2: {:?}",
+            day5::rearrange(&input, &CrateMover9001, cli.lenient)?
+        );
+    } else {
+        let cranes: Vec<_> = cli.cranes.iter().map(|spec| spec.build()).collect();
+        for (name, tops) in day5::run_cranes(&input, &cranes, cli.lenient)? {
+            println!("{name}: {tops:?}");
+        }
+    }
 
-fn parse_instruction(i: &str) -> IResult<&str, Instruction> {
-    map(
-        tuple((
-            preceded(tag("move "), parse_number),
-            preceded(tag(" from "), parse_pile_number),
-            preceded(tag(" to "), parse_pile_number),
-        )),
-        |(quantity, src, dst)| Instruction { quantity, src, dst },
-    )(i)
+    Ok(())
 }
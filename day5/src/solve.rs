@@ -0,0 +1,209 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt,
+};
+
+use crate::{crane::Crane, Crate, Instruction, Piles};
+
+/// Why no procedure could be found between two arrangements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    PileCount {
+        start: usize,
+        goal: usize,
+    },
+    /// The goal doesn't hold exactly the crates of the start, so no crane
+    /// can get there.
+    DifferentCrates,
+    /// Every reachable arrangement was tried without finding the goal.
+    Unreachable,
+    /// The search gave up after seeing this many arrangements.
+    Limit(usize),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::PileCount { start, goal } => {
+                write!(f, "the goal has {goal} piles but the start has {start}")
+            }
+            SolveError::DifferentCrates => {
+                write!(f, "the goal doesn't hold the same crates as the start")
+            }
+            SolveError::Unreachable => write!(f, "the crane can't reach the goal"),
+            SolveError::Limit(limit) => {
+                write!(f, "gave up after trying {limit} arrangements")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Finds a shortest procedure that takes `crane` from `start` to `goal`,
+/// trying at most `limit` arrangements.
+///
+/// Instructions are numbered by their line in the returned procedure,
+/// starting at 1.
+pub fn solve(
+    start: &Piles,
+    goal: &Piles,
+    crane: &dyn Crane,
+    limit: usize,
+) -> Result<Vec<Instruction>, SolveError> {
+    if start.0.len() != goal.0.len() {
+        return Err(SolveError::PileCount {
+            start: start.0.len(),
+            goal: goal.0.len(),
+        });
+    }
+    if sorted_crates(start) != sorted_crates(goal) {
+        return Err(SolveError::DifferentCrates);
+    }
+
+    // breadth first, remembering how each arrangement was first reached
+    let mut came_from: HashMap<Piles, Option<(Piles, Instruction)>> = HashMap::new();
+    came_from.insert(start.clone(), None);
+    let mut queue = VecDeque::from([start.clone()]);
+
+    while let Some(piles) = queue.pop_front() {
+        if &piles == goal {
+            return Ok(path_to(&came_from, piles));
+        }
+        for ins in moves(&piles) {
+            let mut next = piles.clone();
+            if crane.apply(&mut next, &ins).is_err() {
+                continue;
+            }
+            if let Entry::Vacant(entry) = came_from.entry(next.clone()) {
+                entry.insert(Some((piles.clone(), ins)));
+                queue.push_back(next);
+            }
+        }
+        if came_from.len() > limit {
+            return Err(SolveError::Limit(limit));
+        }
+    }
+
+    Err(SolveError::Unreachable)
+}
+
+fn sorted_crates(piles: &Piles) -> Vec<&Crate> {
+    let mut crates: Vec<_> = piles.0.iter().flatten().collect();
+    crates.sort_unstable();
+    crates
+}
+
+/// Every instruction that can be carried out on `piles`.
+fn moves(piles: &Piles) -> impl Iterator<Item = Instruction> + '_ {
+    let count = piles.0.len();
+    (0..count).flat_map(move |src| {
+        (0..count)
+            .filter(move |&dst| dst != src)
+            .flat_map(move |dst| {
+                (1..=piles.0[src].len()).map(move |quantity| Instruction {
+                    quantity,
+                    src,
+                    dst,
+                    line: 0,
+                })
+            })
+    })
+}
+
+/// Walks back from `piles` to the start, numbering the instructions.
+fn path_to(
+    came_from: &HashMap<Piles, Option<(Piles, Instruction)>>,
+    mut piles: Piles,
+) -> Vec<Instruction> {
+    let mut path = Vec::new();
+    while let Some((prev, ins)) = &came_from[&piles] {
+        path.push(ins.clone());
+        piles = prev.clone();
+    }
+    path.reverse();
+    for (i, ins) in path.iter_mut().enumerate() {
+        ins.line = i + 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001, Limited},
+        parse, parse_drawing, EXAMPLE_INPUT,
+    };
+
+    fn replay(start: &Piles, procedure: &[Instruction], crane: &dyn Crane) -> Piles {
+        let mut piles = start.clone();
+        for ins in procedure {
+            crane.apply(&mut piles, ins).unwrap();
+        }
+        piles
+    }
+
+    #[test]
+    fn solves_the_example_in_fewer_moves() {
+        let (start, instructions) = parse(EXAMPLE_INPUT).unwrap();
+        let cranes: [&dyn Crane; 3] = [
+            &CrateMover9000,
+            &CrateMover9001,
+            &Limited::new(CrateMover9001, 2),
+        ];
+        for crane in cranes {
+            let goal = replay(&start, &instructions, crane);
+            let procedure = solve(&start, &goal, crane, 100_000).unwrap();
+
+            assert!(procedure.len() <= instructions.len());
+            assert_eq!(replay(&start, &procedure, crane), goal);
+        }
+    }
+
+    #[test]
+    fn finds_a_shortest_procedure() {
+        let start = parse_drawing("[A]        \n[B]        \n[C]        \n 1   2   3 ").unwrap();
+        let goal = parse_drawing("        [C]\n        [B]\n        [A]\n 1   2   3 ").unwrap();
+
+        let procedure = solve(&start, &goal, &CrateMover9000, 1000).unwrap();
+        assert_eq!(procedure.len(), 1);
+        assert_eq!(procedure[0].to_string(), "move 3 from 1 to 3");
+
+        // keeping the order, the 9001 has to go through the middle pile
+        let procedure = solve(&start, &goal, &CrateMover9001, 1000).unwrap();
+        assert_eq!(procedure.len(), 3);
+        assert_eq!(replay(&start, &procedure, &CrateMover9001), goal);
+        assert_eq!(
+            procedure.iter().map(|ins| ins.line).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn rejects_impossible_goals() {
+        let (start, _) = parse(EXAMPLE_INPUT).unwrap();
+
+        let mut goal = start.clone();
+        goal.0.push(Vec::new());
+        assert_eq!(
+            solve(&start, &goal, &CrateMover9000, 1000),
+            Err(SolveError::PileCount { start: 3, goal: 4 })
+        );
+
+        let mut goal = start.clone();
+        goal.0[2][0] = Crate::from("Q");
+        assert_eq!(
+            solve(&start, &goal, &CrateMover9000, 1000),
+            Err(SolveError::DifferentCrates)
+        );
+
+        let goal = Piles(vec![vec![], vec![], start.0.concat()]);
+        assert_eq!(
+            solve(&start, &goal, &CrateMover9000, 10),
+            Err(SolveError::Limit(10))
+        );
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day5::part1(day5::INPUT).unwrap(), "VQZNJMWTR");
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day5::part2(day5::INPUT).unwrap(), "NLCDCLVMQ");
+}
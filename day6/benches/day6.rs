@@ -0,0 +1,43 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn parts(c: &mut Criterion) {
+    let input = day6::INPUT;
+
+    c.bench_function("day6 part1", |b| b.iter(|| day6::part1(black_box(input))));
+    c.bench_function("day6 part2", |b| b.iter(|| day6::part2(black_box(input))));
+}
+
+/// A signal of `len` bytes whose only marker of `n` characters is at its
+/// very end, so the whole of it has to be scanned.
+fn late_marker(len: usize, n: usize) -> String {
+    let alphabet: Vec<char> = ('!'..='~').collect();
+    let mut signal: String = alphabet[..n - 1].iter().cycle().take(len - n).collect();
+    signal.extend(&alphabet[..n]);
+    signal
+}
+
+fn markers(c: &mut Criterion) {
+    const LEN: usize = 4 << 20;
+
+    let mut group = c.benchmark_group("day6 markers");
+    group
+        .sample_size(10)
+        .throughput(Throughput::Bytes(LEN as u64));
+    for n in [4, 14, 50, 90] {
+        let signal = late_marker(LEN, n);
+        assert_eq!(day6::marker_start(&signal, n), Some(LEN));
+
+        group.bench_with_input(BenchmarkId::new("rolling", n), &signal, |b, s| {
+            b.iter(|| day6::marker_start(black_box(s), n))
+        });
+        group.bench_with_input(BenchmarkId::new("hashset", n), &signal, |b, s| {
+            b.iter(|| day6::marker_start_hashset(black_box(s), n))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parts, markers);
+criterion_main!(benches);
@@ -0,0 +1,147 @@
+use std::{collections::HashSet, fmt};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+/// A signal without `size` different characters in a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoMarker {
+    pub size: usize,
+}
+
+impl fmt::Display for NoMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the signal never has {} different characters in a row",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for NoMarker {}
+
+pub fn part1(input: &str) -> Result<usize, NoMarker> {
+    packet_start(input, 4)
+}
+
+pub fn part2(input: &str) -> Result<usize, NoMarker> {
+    packet_start(input, 14)
+}
+
+fn packet_start(signal: &str, n: usize) -> Result<usize, NoMarker> {
+    marker_start(signal, n).ok_or(NoMarker { size: n })
+}
+
+/// The number of characters read once the last `n` were all different, if
+/// that ever happens.
+///
+/// Slides a window over the signal keeping a count of each byte in it and
+/// of how many of those counts are non-zero, so every step costs the same
+/// whatever `n` is.
+pub fn marker_start(signal: &str, n: usize) -> Option<usize> {
+    let signal = signal.as_bytes();
+    if n == 0 {
+        return Some(0);
+    }
+    // there are only so many different bytes
+    if n > 256 {
+        return None;
+    }
+
+    let mut counts = [0_usize; 256];
+    let mut distinct = 0;
+    for (i, &byte) in signal.iter().enumerate() {
+        counts[byte as usize] += 1;
+        if counts[byte as usize] == 1 {
+            distinct += 1;
+        }
+        if let Some(i) = i.checked_sub(n) {
+            counts[signal[i] as usize] -= 1;
+            if counts[signal[i] as usize] == 0 {
+                distinct -= 1;
+            }
+        }
+        if distinct == n {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+/// [`marker_start`] the straightforward way, building a set for every
+/// window; kept to check and benchmark the other against.
+pub fn marker_start_hashset(signal: &str, n: usize) -> Option<usize> {
+    if n == 0 {
+        return Some(0);
+    }
+    signal
+        .as_bytes()
+        .windows(n)
+        .position(|w| HashSet::<_>::from_iter(w).len() == n)
+        .map(|i| i + n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for (signal, start, _) in EXAMPLES {
+            assert_eq!(part1(signal), Ok(start), "{signal}");
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (signal, _, start) in EXAMPLES {
+            assert_eq!(part2(signal), Ok(start), "{signal}");
+        }
+    }
+
+    #[test]
+    fn agrees_with_hashset_version() {
+        for (signal, _, _) in EXAMPLES {
+            for n in 0..=signal.len() + 1 {
+                assert_eq!(
+                    marker_start(signal, n),
+                    marker_start_hashset(signal, n),
+                    "{signal} with {n}"
+                );
+            }
+        }
+        let input = &INPUT[..3000];
+        for n in [20, 25, 26, 27, 300] {
+            assert_eq!(marker_start(input, n), marker_start_hashset(input, n));
+        }
+    }
+
+    #[test]
+    fn reports_missing_markers() {
+        let err = part1("aaaaaaa").unwrap_err();
+        assert_eq!(err, NoMarker { size: 4 });
+        assert_eq!(
+            err.to_string(),
+            "the signal never has 4 different characters in a row"
+        );
+    }
+
+    #[test]
+    fn handles_short_signals() {
+        assert_eq!(marker_start("", 4), None);
+        assert_eq!(marker_start("abc", 4), None);
+        assert_eq!(marker_start("abcd", 4), Some(4));
+        assert_eq!(marker_start("aaaa", 1), Some(1));
+        assert_eq!(marker_start("abc", 0), Some(0));
+    }
+}
@@ -1,17 +1,10 @@
-use std::collections::HashSet;
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
 
-fn main() {
-    let input = include_str!("input.txt");
+    let input = common::load_input(day6::INPUT, Some(day6::EXAMPLE_INPUT))?;
 
-    println!("Part 1: {}", packet_start(input, 4));
-    println!("Part 2: {}", packet_start(input, 14));
-}
+    println!("Part 1: {}", day6::part1(&input)?);
+    println!("Part 2: {}", day6::part2(&input)?);
 
-fn packet_start(signal: &str, n: usize) -> usize {
-    signal
-        .as_bytes()
-        .windows(n)
-        .position(|w| HashSet::<_>::from_iter(w).len() == n)
-        .map(|i| i + n)
-        .unwrap()
+    Ok(())
 }
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day6::part1(day6::INPUT).unwrap(), 1598);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day6::part2(day6::INPUT).unwrap(), 2414);
+}
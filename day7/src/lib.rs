@@ -0,0 +1,297 @@
+use camino::Utf8PathBuf;
+use color_eyre::eyre::{bail, eyre};
+use common::{IResult, ParseError};
+use id_tree::{InsertBehavior, Node, NodeId, Tree};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    combinator::{cut, map},
+    error::context,
+    sequence::{preceded, separated_pair},
+};
+
+pub static EXAMPLE_INPUT: &str = r#"
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+"#;
+
+#[derive(Debug)]
+struct Ls;
+
+fn parse_ls(i: &str) -> IResult<'_, Ls> {
+    map(tag("ls"), |_| Ls)(i)
+}
+
+#[derive(Debug)]
+struct Cd(Utf8PathBuf);
+
+fn parse_cd(i: &str) -> IResult<'_, Cd> {
+    map(preceded(tag("cd "), parse_path), Cd)(i)
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Ls,
+    Cd(Utf8PathBuf),
+}
+
+impl From<Ls> for Command {
+    fn from(_ls: Ls) -> Self {
+        Command::Ls
+    }
+}
+
+impl From<Cd> for Command {
+    fn from(cd: Cd) -> Self {
+        Command::Cd(cd.0)
+    }
+}
+
+fn parse_command(i: &str) -> IResult<'_, Command> {
+    let (i, _) = tag("$ ")(i)?;
+    cut(context(
+        "`ls` or `cd`",
+        alt((map(parse_ls, Into::into), map(parse_cd, Into::into))),
+    ))(i)
+}
+
+#[derive(Debug)]
+pub enum Entry {
+    Dir(Utf8PathBuf),
+    File(u64, Utf8PathBuf),
+}
+
+fn parse_entry(i: &str) -> IResult<'_, Entry> {
+    let parse_file = map(
+        separated_pair(nom::character::complete::u64, tag(" "), cut(parse_path)),
+        |(size, path)| Entry::File(size, path),
+    );
+    let parse_dir = map(preceded(tag("dir "), cut(parse_path)), Entry::Dir);
+
+    alt((parse_file, parse_dir))(i)
+}
+
+#[derive(Debug)]
+pub enum Line {
+    Command(Command),
+    Entry(Entry),
+}
+
+fn parse_line(i: &str) -> IResult<'_, Line> {
+    context(
+        "command, `dir` or file size",
+        alt((
+            map(parse_command, Line::Command),
+            map(parse_entry, Line::Entry),
+        )),
+    )(i)
+}
+
+#[derive(Debug)]
+pub struct FsEntry {
+    pub path: Utf8PathBuf,
+    pub size: u64,
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> color_eyre::Result<u64> {
+    let tree = build_tree(input)?;
+
+    let sum = dir_sizes(&tree)?
+        .into_iter()
+        .filter(|&size| size <= 100_000)
+        .sum::<u64>();
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> color_eyre::Result<u64> {
+    let tree = build_tree(input)?;
+
+    let total_space = 70_000_000_u64;
+    let space_needed = 30_000_000_u64;
+
+    let space_used = total_size(&tree, tree.get(root_id(&tree)?)?)?;
+    let Some(space_unused) = total_space.checked_sub(space_used) else {
+        bail!("the files take up {space_used} bytes, more than the disk's {total_space}");
+    };
+    // nothing needs deleting if there is already enough room
+    let min_space_to_free = space_needed.saturating_sub(space_unused);
+    if min_space_to_free == 0 {
+        return Ok(0);
+    }
+
+    let dir_to_delete_size = dir_sizes(&tree)?
+        .into_iter()
+        .filter(|&size| size >= min_space_to_free)
+        .min()
+        .ok_or_else(|| eyre!("no directory frees {min_space_to_free} bytes"))?;
+
+    Ok(dir_to_delete_size)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    Ok(parse_numbered(input)?
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect())
+}
+
+/// Parses the non-empty lines of `input` along with their 1-based numbers.
+fn parse_numbered(input: &str) -> Result<Vec<(usize, Line)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|(i, l)| Ok((i + 1, common::parse_line(i + 1, l, parse_line)?)))
+        .collect()
+}
+
+pub fn build_tree(input: &str) -> color_eyre::Result<Tree<FsEntry>> {
+    let lines = parse_numbered(input)?;
+
+    let mut tree = Tree::new();
+    let root = tree.insert(
+        Node::new(FsEntry {
+            path: "/".into(),
+            size: 0,
+        }),
+        InsertBehavior::AsRoot,
+    )?;
+    let mut curr = root;
+
+    for (line_no, line) in lines {
+        match line {
+            Line::Command(cmd) => match cmd {
+                Command::Ls => {
+                    // ignore for now
+                }
+                Command::Cd(path) => match path.as_str() {
+                    "/" => {
+                        // ignore for now
+                    }
+                    ".." => {
+                        curr = tree
+                            .get(&curr)?
+                            .parent()
+                            .ok_or_else(|| eyre!("line {line_no}: `cd ..` at the root"))?
+                            .clone();
+                    }
+                    _ => {
+                        let node = Node::new(FsEntry {
+                            path: path.clone(),
+                            size: 0,
+                        });
+                        curr = tree.insert(node, InsertBehavior::UnderNode(&curr))?;
+                    }
+                },
+            },
+            Line::Entry(entry) => match entry {
+                Entry::Dir(_path) => {
+                    // ignore for now
+                }
+                Entry::File(size, path) => {
+                    let node = Node::new(FsEntry { path, size });
+                    tree.insert(node, InsertBehavior::UnderNode(&curr))?;
+                }
+            },
+        }
+    }
+
+    Ok(tree)
+}
+
+fn parse_path(i: &str) -> IResult<'_, Utf8PathBuf> {
+    context(
+        "path",
+        map(
+            take_while1(|c: char| "abcdefghijklmnopqrstuvwxyz./".contains(c)),
+            Into::into,
+        ),
+    )(i)
+}
+
+fn root_id(tree: &Tree<FsEntry>) -> color_eyre::Result<&NodeId> {
+    tree.root_node_id()
+        .ok_or_else(|| eyre!("the file system has no root"))
+}
+
+/// The total size of every directory holding anything.
+fn dir_sizes(tree: &Tree<FsEntry>) -> color_eyre::Result<Vec<u64>> {
+    tree.traverse_pre_order(root_id(tree)?)?
+        .filter(|node| !node.children().is_empty())
+        .map(|node| total_size(tree, node))
+        .collect()
+}
+
+fn total_size(tree: &Tree<FsEntry>, node: &Node<FsEntry>) -> color_eyre::Result<u64> {
+    let mut total = node.data().size;
+    for child in node.children() {
+        total += total_size(tree, tree.get(child)?)?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 95437);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn part2_with_enough_room() {
+        assert_eq!(part2("$ cd /\n$ ls\n100 a\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn part2_rejects_overfull_disks() {
+        let err = part2("$ cd /\n$ ls\n70000001 a\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the files take up 70000001 bytes, more than the disk's 70000000"
+        );
+    }
+
+    #[test]
+    fn rejects_leaving_the_root() {
+        let err = build_tree("$ cd /\n\n$ cd ..\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3: `cd ..` at the root");
+    }
+
+    #[test]
+    fn rejects_unknown_command() {
+        let err = parse("$ cd /\n$ rm -rf a\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "`ls` or `cd`");
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day7::part1(day7::INPUT).unwrap(), 1297159);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day7::part2(day7::INPUT).unwrap(), 3866390);
+}
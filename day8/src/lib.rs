@@ -0,0 +1,154 @@
+use std::fmt::{self, Debug, Formatter};
+
+use common::ParseError;
+use grid::{Direction, Grid, GridPos};
+
+pub struct TreeMap(Grid<u32>);
+
+impl Debug for TreeMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TreeMap {
+    fn height(&self, pos: GridPos) -> u32 {
+        *self.0.get(pos).unwrap()
+    }
+
+    fn walkable(&self, pos: GridPos, direction: Direction) -> bool {
+        let base_tree = self.height(pos);
+
+        self.0
+            .ray(pos, direction)
+            .all(|(_, &tree)| tree < base_tree)
+    }
+
+    fn viewing_distance(&self, pos: GridPos, direction: Direction) -> usize {
+        let base_tree = self.height(pos);
+
+        let mut visible = 0;
+        for (_, &tree) in self.0.ray(pos, direction) {
+            visible += 1;
+
+            if tree >= base_tree {
+                break;
+            }
+        }
+
+        visible
+    }
+
+    fn is_visible(&self, pos: GridPos) -> bool {
+        Direction::ALL
+            .into_iter()
+            .any(|direction| self.walkable(pos, direction))
+    }
+
+    fn scenic_score(&self, pos: GridPos) -> usize {
+        Direction::ALL
+            .into_iter()
+            .map(|direction| self.viewing_distance(pos, direction))
+            .product()
+    }
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub static EXAMPLE_INPUT: &str = r#"
+30373
+25512
+65332
+33549
+35390
+"#;
+
+pub fn parse(input: &str) -> Result<TreeMap, ParseError> {
+    let mut width = None;
+    let mut cells = Vec::new();
+
+    for (i, line) in input.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+        for (pos, c) in line.char_indices() {
+            let height = char::to_digit(c, 10)
+                .ok_or_else(|| ParseError::at(i + 1, line, &line[pos..], "tree height"))?;
+            cells.push(height);
+        }
+
+        let row_width = line.chars().count();
+        match width {
+            None => width = Some(row_width),
+            Some(width) if width == row_width => {}
+            Some(width) => {
+                let expected = format!("{width} trees per row");
+                return Err(if row_width < width {
+                    ParseError::at_end(i + 1, line, expected)
+                } else {
+                    let (pos, _) = line.char_indices().nth(width).unwrap();
+                    ParseError::at(i + 1, line, &line[pos..], expected)
+                });
+            }
+        }
+    }
+
+    match width {
+        Some(width) => Ok(TreeMap(Grid::new(width, cells))),
+        None => Err(ParseError::new(1, 1, "tree heights", "")),
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let tree_map = parse(input)?;
+
+    let walkable_trees = tree_map
+        .0
+        .positions()
+        .filter(|&pos| tree_map.is_visible(pos))
+        .count();
+
+    Ok(walkable_trees)
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let tree_map = parse(input)?;
+
+    let max_scenic_score = tree_map
+        .0
+        .positions()
+        .map(|pos| tree_map.scenic_score(pos))
+        .max()
+        .unwrap();
+
+    Ok(max_scenic_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 8);
+    }
+
+    #[test]
+    fn scenic_scores() {
+        let tree_map = parse(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(tree_map.scenic_score(GridPos::new(2, 1)), 4);
+        assert_eq!(tree_map.scenic_score(GridPos::new(2, 3)), 8);
+        assert!(tree_map.is_visible(GridPos::new(1, 1)));
+        assert!(!tree_map.is_visible(GridPos::new(3, 1)));
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = parse("303\n25\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "3 trees per row");
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day8::part1(day8::INPUT).unwrap(), 1789);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day8::part2(day8::INPUT).unwrap(), 314820);
+}
@@ -1,10 +1,8 @@
 use std::{collections::VecDeque, time::Duration};
 
+use day9::parse::Instruction;
 use egui::{Color32, Sense, Slider, Stroke};
-use nom::{combinator::all_consuming, Finish};
-use parse::{Direction, GridPos, Instruction};
-
-mod parse;
+use grid::{Direction, GridPos};
 
 use eframe::{egui, epaint::ahash::HashSet};
 
@@ -58,15 +56,14 @@ struct MyApp {
 
 impl MyApp {
     fn new() -> Self {
-        let instructions = include_str!("input.txt")
-            .lines()
-            .map(|l| all_consuming(Instruction::parse)(l).finish().unwrap().1)
-            .collect();
+        let instructions = day9::parse_instructions(day9::INPUT)
+            .expect("embedded input is valid")
+            .into();
 
         Self {
             instructions,
-            head: GridPos { x: 0, y: 0 },
-            tail: GridPos { x: 0, y: 0 },
+            head: GridPos::ORIGIN,
+            tail: GridPos::ORIGIN,
             tail_visited: Default::default(),
             speed: 1,
             paused: true,
@@ -83,34 +80,7 @@ impl MyApp {
         };
         self.head += instruction.dir.delta();
 
-        let diff = self.head - self.tail;
-        let (dx, dy) = match (diff.x, diff.y) {
-            // overlapping
-            (0, 0) => (0, 0),
-            // touching up/left/down/right
-            (0, 1) | (1, 0) | (0, -1) | (-1, 0) => (0, 0),
-            // touching diagonally
-            (1, 1) | (1, -1) | (-1, 1) | (-1, -1) => (0, 0),
-            // need to move up/left/down/right
-            (0, 2) => (0, 1),
-            (0, -2) => (0, -1),
-            (2, 0) => (1, 0),
-            (-2, 0) => (-1, 0),
-            // need to move to the right diagonally
-            (2, 1) => (1, 1),
-            (2, -1) => (1, -1),
-            // need to move to the left diagonally
-            (-2, 1) => (-1, 1),
-            (-2, -1) => (-1, -1),
-            // need to move up/down diagonally
-            (1, 2) => (1, 1),
-            (-1, 2) => (-1, 1),
-            (1, -2) => (1, -1),
-            (-1, -2) => (-1, -1),
-            _ => panic!("unhandled case: tail - head = {diff:?}"),
-        };
-        self.tail.x += dx;
-        self.tail.y += dy;
+        self.tail = day9::follow(self.head, self.tail);
         self.tail_visited.insert(self.tail);
 
         instruction.dist -= 1;
@@ -215,7 +185,7 @@ impl eframe::App for MyApp {
             for x in -half_width..half_width {
                 for y in -half_height..half_height {
                     let dot = GridPos { x, y };
-                    let color = if dot.x == 0 && dot.y == 0 {
+                    let color = if dot == GridPos::ORIGIN {
                         Color32::WHITE
                     } else if self.tail_visited.contains(&dot) {
                         Color32::DARK_RED
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use common::{parse_line, ParseError};
+use grid::GridPos;
+use parse::Instruction;
+
+pub static EXAMPLE_INPUT: &str = r#"
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+"#;
+
+pub mod parse;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|(i, l)| parse_line(i + 1, l, Instruction::parse))
+        .collect()
+}
+
+/// Where a knot ends up after the knot it is tied to moved to `leader`: it
+/// stays put while touching (diagonals included), otherwise it takes one step
+/// straight or diagonally towards the leader.
+pub fn follow(leader: GridPos, knot: GridPos) -> GridPos {
+    let diff = leader - knot;
+    if diff.chebyshev(GridPos::ORIGIN) > 1 {
+        knot + diff.signum()
+    } else {
+        knot
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    simulate(input, 2)
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    simulate(input, 10)
+}
+
+/// Moves a rope of `len` knots and counts the positions its tail visits.
+fn simulate(input: &str, len: usize) -> Result<usize, ParseError> {
+    let mut knots = vec![GridPos::ORIGIN; len];
+
+    let mut visited_by_tail = HashSet::new();
+    visited_by_tail.insert(GridPos::ORIGIN);
+    for ins in parse_instructions(input)? {
+        for _ in 0..ins.dist {
+            knots[0] += ins.dir.delta();
+            // follow the previous knot
+            for i in 1..len {
+                knots[i] = follow(knots[i - 1], knots[i]);
+            }
+
+            visited_by_tail.insert(knots[len - 1]);
+        }
+    }
+
+    Ok(visited_by_tail.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SMALL_EXAMPLE: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(SMALL_EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2(SMALL_EXAMPLE).unwrap(), 1);
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 36);
+    }
+
+    #[test]
+    fn knots_follow_their_leader() {
+        let knot = GridPos::ORIGIN;
+
+        // touching, diagonals included
+        assert_eq!(follow(GridPos::new(1, 1), knot), knot);
+        assert_eq!(follow(GridPos::new(2, 0), knot), GridPos::new(1, 0));
+        assert_eq!(follow(GridPos::new(-1, 2), knot), GridPos::new(-1, 1));
+        assert_eq!(follow(GridPos::new(2, -2), knot), GridPos::new(1, -1));
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn part1() {
+    assert_eq!(day9::part1(day9::INPUT).unwrap(), 6026);
+}
+
+#[test]
+fn part2() {
+    assert_eq!(day9::part2(day9::INPUT).unwrap(), 2273);
+}
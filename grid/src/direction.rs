@@ -0,0 +1,28 @@
+use crate::GridPos;
+
+/// A step along one axis. `y` grows downwards, like rows on screen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn delta(self) -> GridPos {
+        match self {
+            Direction::Up => GridPos { x: 0, y: -1 },
+            Direction::Down => GridPos { x: 0, y: 1 },
+            Direction::Left => GridPos { x: -1, y: 0 },
+            Direction::Right => GridPos { x: 1, y: 0 },
+        }
+    }
+}
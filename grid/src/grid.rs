@@ -0,0 +1,149 @@
+use std::fmt;
+
+use crate::{Direction, GridPos};
+
+/// A dense, row-major grid with its origin in the top-left corner.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    ///
+    /// Panics if `cells` doesn't split evenly into rows of `width`.
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {width}",
+            cells.len()
+        );
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: GridPos) -> bool {
+        self.index(pos).is_some()
+    }
+
+    pub fn get(&self, pos: GridPos) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: GridPos) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = GridPos> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| GridPos { x, y }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GridPos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells walked through when heading in `dir` from `from` until the
+    /// edge of the grid, not including `from` itself.
+    pub fn ray(&self, from: GridPos, dir: Direction) -> impl Iterator<Item = (GridPos, &T)> {
+        let delta = dir.delta();
+        std::iter::successors(Some(from + delta), move |&pos| Some(pos + delta))
+            .map_while(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    /// The in-bounds neighbours up, down, left and right of `pos`.
+    pub fn neighbours4(&self, pos: GridPos) -> impl Iterator<Item = (GridPos, &T)> {
+        pos.neighbours4()
+            .filter_map(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    /// The in-bounds neighbours of `pos`, diagonals included.
+    pub fn neighbours8(&self, pos: GridPos) -> impl Iterator<Item = (GridPos, &T)> {
+        pos.neighbours8()
+            .filter_map(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    fn index(&self, pos: GridPos) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::new(3, (1..=6).collect())
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(GridPos::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(GridPos::new(3, 0)), None);
+        assert_eq!(grid.get(GridPos::new(0, -1)), None);
+    }
+
+    #[test]
+    fn rays_stop_at_the_edge() {
+        let grid = grid();
+        let ray = |pos, dir| grid.ray(pos, dir).map(|(_, &c)| c).collect::<Vec<_>>();
+
+        assert_eq!(ray(GridPos::new(0, 0), Direction::Right), [2, 3]);
+        assert_eq!(ray(GridPos::new(1, 1), Direction::Up), [2]);
+        assert_eq!(ray(GridPos::new(1, 1), Direction::Down), []);
+    }
+
+    #[test]
+    fn neighbours_in_bounds() {
+        let grid = grid();
+
+        assert_eq!(grid.neighbours4(GridPos::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(GridPos::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(grid().to_string(), "123\n456\n");
+    }
+}
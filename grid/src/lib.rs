@@ -0,0 +1,7 @@
+mod direction;
+mod grid;
+mod pos;
+
+pub use direction::Direction;
+pub use grid::Grid;
+pub use pos::GridPos;
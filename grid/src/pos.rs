@@ -0,0 +1,147 @@
+use std::{fmt, ops};
+
+use crate::Direction;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridPos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl fmt::Debug for GridPos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl GridPos {
+    pub const ORIGIN: GridPos = GridPos { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: GridPos) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps are allowed, i.e. the number of king
+    /// moves between the two positions.
+    pub fn chebyshev(self, other: GridPos) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Clamps both components to -1, 0 or 1: a single step towards `self`.
+    pub fn signum(self) -> GridPos {
+        GridPos {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    /// The positions up, down, left and right of this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = GridPos> {
+        Direction::ALL
+            .into_iter()
+            .map(move |dir| self + dir.delta())
+    }
+
+    /// All eight positions surrounding this one, diagonals included.
+    pub fn neighbours8(self) -> impl Iterator<Item = GridPos> {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| GridPos { x, y }))
+            .filter(|&delta| delta != GridPos::ORIGIN)
+            .map(move |delta| self + delta)
+    }
+}
+
+impl ops::Add for GridPos {
+    type Output = GridPos;
+
+    fn add(self, other: GridPos) -> GridPos {
+        GridPos {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl ops::AddAssign for GridPos {
+    fn add_assign(&mut self, other: GridPos) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for GridPos {
+    type Output = GridPos;
+
+    fn sub(self, other: GridPos) -> GridPos {
+        GridPos {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl ops::SubAssign for GridPos {
+    fn sub_assign(&mut self, other: GridPos) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Neg for GridPos {
+    type Output = GridPos;
+
+    fn neg(self) -> GridPos {
+        GridPos {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl ops::Mul<i32> for GridPos {
+    type Output = GridPos;
+
+    fn mul(self, factor: i32) -> GridPos {
+        GridPos {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = GridPos::new(3, -2);
+        let b = GridPos::new(-1, 4);
+
+        assert_eq!(a + b, GridPos::new(2, 2));
+        assert_eq!(a - b, GridPos::new(4, -6));
+        assert_eq!(-a, GridPos::new(-3, 2));
+        assert_eq!(a * 2, GridPos::new(6, -4));
+        assert_eq!((a - b).signum(), GridPos::new(1, -1));
+    }
+
+    #[test]
+    fn distances() {
+        let a = GridPos::new(3, -2);
+        let b = GridPos::new(-1, 4);
+
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+    }
+
+    #[test]
+    fn neighbours() {
+        let pos = GridPos::new(5, 5);
+
+        assert_eq!(pos.neighbours4().count(), 4);
+        assert!(pos.neighbours8().all(|n| n.chebyshev(pos) == 1));
+        assert_eq!(pos.neighbours8().count(), 8);
+    }
+}